@@ -0,0 +1,454 @@
+//! Principal branches of the elementary functions.
+//!
+//! Branch cuts are listed on each function. On a cut the sign of the zero component picks
+//! the side, as in C99: a positive zero imaginary part gives the limit from above and a
+//! negative one (`-0.0`, or a negated `Decimal` zero) the limit from below; on cuts along
+//! the imaginary axis the sign of the zero real part picks right or left likewise.
+
+use super::{Complex, Real};
+
+impl<T: Real> Complex<T> {
+    /// `e^z`.
+    pub fn exp(&self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Principal natural logarithm `ln|z| + i·arg z`, with the imaginary part in `(-π, π]`.
+    ///
+    /// Branch cut: `(-∞, 0)`. `ln 0` is `-∞` for floats and panics for `Decimal`.
+    pub fn ln(&self) -> Self {
+        Complex {
+            re: self.norm().ln(),
+            im: self.arg(),
+        }
+    }
+
+    /// Logarithm to a real `base`, `ln z / ln base`. Same branch cut as [`Complex::ln`].
+    pub fn log(&self, base: T) -> Self {
+        self.ln() / base.ln()
+    }
+
+    /// Principal square root, the one with a non-negative real part.
+    ///
+    /// Branch cut: `(-∞, 0)`.
+    pub fn sqrt(&self) -> Self {
+        if self.re.is_zero() && self.im.is_zero() {
+            return *self;
+        }
+
+        // t = √((|re| + |z|)/2), halving before the sum so it cannot overflow.
+        let two = T::one() + T::one();
+        let t = (self.re.abs() / two + self.norm() / two).sqrt();
+        match self.re.is_sign_negative() {
+            false => Complex {
+                re: t,
+                im: self.im / (two * t),
+            },
+            true => Complex {
+                re: self.im.abs() / (two * t),
+                im: match self.im.is_sign_negative() {
+                    true => -t,
+                    false => t,
+                },
+            },
+        }
+    }
+
+    /// Principal cube root `|z|^(1/3)·e^(i·arg z/3)`. Negative reals give the root at
+    /// angle `π/3`, not the real one.
+    ///
+    /// Branch cut: `(-∞, 0)`.
+    pub fn cbrt(&self) -> Self {
+        self.powf(T::one() / (T::one() + T::one() + T::one()))
+    }
+
+    /// Principal power `e^(x·ln z)` to a real exponent. `0^0` is one and `0^x` zero
+    /// otherwise.
+    ///
+    /// Branch cut: `(-∞, 0)`.
+    pub fn powf(&self, x: T) -> Self {
+        if self.re.is_zero() && self.im.is_zero() {
+            return zero_power(x.is_zero());
+        }
+        Self::from_polar((x * self.norm().ln()).exp(), x * self.arg())
+    }
+
+    /// Principal power `e^(w·ln z)` to a complex exponent. `0^0` is one and `0^w` zero
+    /// otherwise.
+    ///
+    /// Branch cut: `(-∞, 0)`.
+    pub fn powc(&self, w: &Complex<T>) -> Self {
+        if self.re.is_zero() && self.im.is_zero() {
+            return zero_power(w.re.is_zero() && w.im.is_zero());
+        }
+        (*w * self.ln()).exp()
+    }
+
+    pub fn sin(&self) -> Self {
+        Complex {
+            re: self.re.sin() * self.im.cosh(),
+            im: self.re.cos() * self.im.sinh(),
+        }
+    }
+
+    pub fn cos(&self) -> Self {
+        Complex {
+            re: self.re.cos() * self.im.cosh(),
+            im: -(self.re.sin() * self.im.sinh()),
+        }
+    }
+
+    /// Written in terms of `e^(-2|im|)` so that large imaginary parts tend to `±i` rather
+    /// than overflowing `sinh`/`cosh`.
+    pub fn tan(&self) -> Self {
+        let (one, two) = (T::one(), T::one() + T::one());
+        let (a, b) = (two * self.re, two * self.im);
+        let e = (-b.abs()).exp();
+        let e2 = e * e;
+        let sech = two * e / (one + e2);
+        let tanh = match b.is_sign_negative() {
+            true => (e2 - one) / (one + e2),
+            false => (one - e2) / (one + e2),
+        };
+        let denominator = one + a.cos() * sech;
+        Complex {
+            re: a.sin() * sech / denominator,
+            im: tanh / denominator,
+        }
+    }
+
+    pub fn sinh(&self) -> Self {
+        Complex {
+            re: self.re.sinh() * self.im.cos(),
+            im: self.re.cosh() * self.im.sin(),
+        }
+    }
+
+    pub fn cosh(&self) -> Self {
+        Complex {
+            re: self.re.cosh() * self.im.cos(),
+            im: self.re.sinh() * self.im.sin(),
+        }
+    }
+
+    /// `-i·tan(iz)`, so large real parts tend to `±1`.
+    pub fn tanh(&self) -> Self {
+        div_i(&mul_i(self).tan())
+    }
+
+    /// Principal arcsine, `-i·asinh(iz)`, with the real part in `[-π/2, π/2]`.
+    ///
+    /// Branch cuts: `(-∞, -1)` and `(1, ∞)`.
+    pub fn asin(&self) -> Self {
+        div_i(&mul_i(self).asinh())
+    }
+
+    /// Principal arccosine, `π/2 - asin z`, with the real part in `[0, π]`.
+    ///
+    /// Branch cuts: `(-∞, -1)` and `(1, ∞)`.
+    pub fn acos(&self) -> Self {
+        let half_pi = T::pi() / (T::one() + T::one());
+        let asin = self.asin();
+        Complex {
+            re: half_pi - asin.re,
+            im: -asin.im,
+        }
+    }
+
+    /// Principal arctangent, `-i·atanh(iz)`, with the real part in `[-π/2, π/2]`.
+    ///
+    /// Branch cuts: `(-i∞, -i)` and `(i, i∞)`; `±i` are poles.
+    pub fn atan(&self) -> Self {
+        div_i(&mul_i(self).atanh())
+    }
+
+    /// Principal inverse hyperbolic sine `ln(z + √(z² + 1))`, with the imaginary part in
+    /// `[-π/2, π/2]`. Evaluated on the right half-plane and mirrored with
+    /// `asinh(-z) = -asinh(z)` to avoid cancellation.
+    ///
+    /// Branch cuts: `(-i∞, -i)` and `(i, i∞)`.
+    pub fn asinh(&self) -> Self {
+        if self.re.is_sign_negative() && !self.re.is_zero() {
+            return negate(&negate(self).asinh());
+        }
+        (*self + shift(&(*self * *self), T::one()).sqrt()).ln()
+    }
+
+    /// Principal inverse hyperbolic cosine `2·ln(√((z + 1)/2) + √((z - 1)/2))`, with a
+    /// non-negative real part and the imaginary part in `[-π, π]`.
+    ///
+    /// Branch cut: `(-∞, 1)`.
+    pub fn acosh(&self) -> Self {
+        let one = T::one();
+        let sum = halve(&shift(self, one)).sqrt() + halve(&shift(self, -one)).sqrt();
+        let ln = sum.ln();
+        ln + ln
+    }
+
+    /// Principal inverse hyperbolic tangent `(ln(1 + z) - ln(1 - z))/2`, with the imaginary
+    /// part in `[-π/2, π/2]`.
+    ///
+    /// Branch cuts: `(-∞, -1)` and `(1, ∞)`; `±1` are poles.
+    pub fn atanh(&self) -> Self {
+        let one = T::one();
+        halve(&(shift(self, one).ln() - shift(&negate(self), one).ln()))
+    }
+}
+
+fn zero_power<T: Real>(exponent_is_zero: bool) -> Complex<T> {
+    Complex {
+        re: match exponent_is_zero {
+            true => T::one(),
+            false => T::zero(),
+        },
+        im: T::zero(),
+    }
+}
+
+/// `-z`, keeping the sign of zero components unlike the `Neg` operator for `T: Num`.
+fn negate<T: Real>(z: &Complex<T>) -> Complex<T> {
+    Complex {
+        re: -z.re,
+        im: -z.im,
+    }
+}
+
+/// `z + x`, leaving the imaginary part and so the sign of a zero untouched.
+fn shift<T: Real>(z: &Complex<T>, x: T) -> Complex<T> {
+    Complex {
+        re: z.re + x,
+        im: z.im,
+    }
+}
+
+fn halve<T: Real>(z: &Complex<T>) -> Complex<T> {
+    let two = T::one() + T::one();
+    Complex {
+        re: z.re / two,
+        im: z.im / two,
+    }
+}
+
+/// `i·z`.
+fn mul_i<T: Real>(z: &Complex<T>) -> Complex<T> {
+    Complex {
+        re: -z.im,
+        im: z.re,
+    }
+}
+
+/// `-i·z`.
+fn div_i<T: Real>(z: &Complex<T>) -> Complex<T> {
+    Complex {
+        re: z.im,
+        im: -z.re,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_3, PI};
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex { re, im }
+    }
+
+    fn assert_close(a: Complex<f64>, b: Complex<f64>, tol: f64) {
+        assert!(
+            (a.re - b.re).abs() < tol && (a.im - b.im).abs() < tol,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    const SAMPLES: [(f64, f64); 8] = [
+        (0.5, 0.25),
+        (-1.5, 0.75),
+        (2.0, -3.0),
+        (-0.3, -0.2),
+        (0.0, 1.5),
+        (4.0, 0.0),
+        (-0.7, 0.0),
+        (1e-3, -2.5),
+    ];
+
+    #[test]
+    fn real_axis_test() {
+        for x in [-2.5f64, -0.4, 0.0, 0.3, 1.7] {
+            let z = c(x, 0.0);
+            assert_close(z.exp(), c(x.exp(), 0.0), 1e-15 * x.exp());
+            assert_close(z.sin(), c(x.sin(), 0.0), 1e-15);
+            assert_close(z.cos(), c(x.cos(), 0.0), 1e-15);
+            assert_close(z.tan(), c(x.tan(), 0.0), 1e-13);
+            assert_close(z.sinh(), c(x.sinh(), 0.0), 1e-14);
+            assert_close(z.cosh(), c(x.cosh(), 0.0), 1e-14);
+            assert_close(z.tanh(), c(x.tanh(), 0.0), 1e-15);
+            assert_close(z.asinh(), c(x.asinh(), 0.0), 1e-15);
+            assert_close(z.atan(), c(x.atan(), 0.0), 1e-15);
+        }
+        for x in [0.2f64, 1.0, 3.5] {
+            let z = c(x, 0.0);
+            assert_close(z.ln(), c(x.ln(), 0.0), 1e-15);
+            assert_close(z.sqrt(), c(x.sqrt(), 0.0), 1e-15);
+            assert_close(z.cbrt(), c(x.cbrt(), 0.0), 1e-15);
+            assert_close(z.log(10.0), c(x.log10(), 0.0), 1e-15);
+            assert_close(z.powf(2.5), c(x.powf(2.5), 0.0), 1e-13);
+        }
+        for x in [-0.9f64, 0.0, 0.6] {
+            let z = c(x, 0.0);
+            assert_close(z.asin(), c(x.asin(), 0.0), 1e-15);
+            assert_close(z.acos(), c(x.acos(), 0.0), 1e-15);
+            assert_close(z.atanh(), c(x.atanh(), 0.0), 1e-15);
+        }
+        assert_close(c(2.0, 0.0).acosh(), c(2.0f64.acosh(), 0.0), 1e-15);
+    }
+
+    #[test]
+    fn known_values_test() {
+        assert_close(c(0.0, PI).exp(), c(-1.0, 0.0), 1e-15);
+        assert_close(c(0.0, 1.0).ln(), c(0.0, FRAC_PI_2), 1e-15);
+        assert_close(c(0.0, 2.0).sqrt(), c(1.0, 1.0), 1e-15);
+        assert_close(c(-8.0, 0.0).cbrt(), c(1.0, 3.0f64.sqrt()), 1e-15);
+        // i^i = e^(-π/2)
+        assert_close(
+            c(0.0, 1.0).powc(&c(0.0, 1.0)),
+            c((-FRAC_PI_2).exp(), 0.0),
+            1e-15,
+        );
+        assert_close(c(1.0, 1.0).powf(2.0), c(0.0, 2.0), 1e-15);
+        assert_close(c(0.0, 1.0).sin(), c(0.0, 1.0f64.sinh()), 1e-15);
+        assert_close(c(0.0, 1.0).cosh(), c(1.0f64.cos(), 0.0), 1e-15);
+        assert_close(c(8.0, -6.0).log(10.0), c(1.0, -0.2794689806475475), 1e-15);
+    }
+
+    #[test]
+    fn zero_power_test() {
+        let zero = c(0.0, 0.0);
+        assert_eq!(zero.powf(0.0), c(1.0, 0.0));
+        assert_eq!(zero.powf(2.5), c(0.0, 0.0));
+        assert_eq!(zero.powc(&c(0.0, 0.0)), c(1.0, 0.0));
+        assert_eq!(zero.powc(&c(1.0, 1.0)), c(0.0, 0.0));
+        assert_eq!(zero.sqrt(), zero);
+    }
+
+    #[test]
+    fn identities_test() {
+        for (re, im) in SAMPLES {
+            let z = c(re, im);
+            assert_close(z.ln().exp(), z, 1e-14);
+            assert_close(z.sqrt() * z.sqrt(), z, 1e-14);
+            assert_close(z.cbrt() * z.cbrt() * z.cbrt(), z, 1e-14);
+            let (s, co) = (z.sin(), z.cos());
+            assert_close(s * s + co * co, c(1.0, 0.0), 1e-13);
+            assert_close(z.tan(), s / co, 1e-13);
+            assert_close(z.tanh(), z.sinh() / z.cosh(), 1e-13);
+            assert_close(z.asin().sin(), z, 1e-13);
+            assert_close(z.acos().cos(), z, 1e-13);
+            assert_close(z.atan().tan(), z, 1e-13);
+            assert_close(z.asinh().sinh(), z, 1e-13);
+            assert_close(z.acosh().cosh(), z, 1e-13);
+            assert_close(z.atanh().tanh(), z, 1e-13);
+            assert_close(z.powc(&c(0.5, 0.0)), z.sqrt(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn principal_ranges_test() {
+        for (re, im) in SAMPLES {
+            let z = c(re, im);
+            assert!(z.sqrt().re >= 0.0);
+            assert!(z.ln().im.abs() <= PI);
+            assert!(z.asin().re.abs() <= FRAC_PI_2);
+            assert!((0.0..=PI).contains(&z.acos().re));
+            assert!(z.atan().re.abs() <= FRAC_PI_2);
+            assert!(z.asinh().im.abs() <= FRAC_PI_2);
+            assert!(z.acosh().re >= 0.0 && z.acosh().im.abs() <= PI);
+            assert!(z.atanh().im.abs() <= FRAC_PI_2);
+        }
+    }
+
+    #[test]
+    fn branch_cut_test() {
+        // ±0 selects the limit from above/below (right/left for imaginary-axis cuts).
+        let (a, b) = (1.3169578969248166, 0.5493061443340549);
+        assert_eq!(c(-2.0, 0.0).ln().im, PI);
+        assert_eq!(c(-2.0, -0.0).ln().im, -PI);
+        assert_eq!(c(-4.0, 0.0).sqrt(), c(0.0, 2.0));
+        assert_eq!(c(-4.0, -0.0).sqrt(), c(0.0, -2.0));
+        assert_close(c(-8.0, -0.0).cbrt(), c(1.0, -(3.0f64.sqrt())), 1e-15);
+        assert_close(c(2.0, 0.0).asin(), c(FRAC_PI_2, a), 1e-15);
+        assert_close(c(2.0, -0.0).asin(), c(FRAC_PI_2, -a), 1e-15);
+        assert_close(c(-2.0, 0.0).asin(), c(-FRAC_PI_2, a), 1e-15);
+        assert_close(c(2.0, 0.0).acos(), c(0.0, -a), 1e-15);
+        assert_close(c(-2.0, -0.0).acos(), c(PI, a), 1e-15);
+        assert_close(c(0.0, 2.0).atan(), c(FRAC_PI_2, b), 1e-15);
+        assert_close(c(-0.0, 2.0).atan(), c(-FRAC_PI_2, b), 1e-15);
+        assert_close(c(0.0, -2.0).atan(), c(FRAC_PI_2, -b), 1e-15);
+        assert_close(c(0.0, 2.0).asinh(), c(a, FRAC_PI_2), 1e-15);
+        assert_close(c(-0.0, -2.0).asinh(), c(-a, -FRAC_PI_2), 1e-15);
+        assert_close(c(0.5, 0.0).acosh(), c(0.0, FRAC_PI_3), 1e-15);
+        assert_close(c(-2.0, 0.0).acosh(), c(a, PI), 1e-15);
+        assert_close(c(-2.0, -0.0).acosh(), c(a, -PI), 1e-15);
+        assert_close(c(2.0, 0.0).atanh(), c(b, FRAC_PI_2), 1e-15);
+        assert_close(c(2.0, -0.0).atanh(), c(b, -FRAC_PI_2), 1e-15);
+        assert_close(c(-2.0, 0.0).atanh(), c(-b, FRAC_PI_2), 1e-15);
+    }
+
+    #[test]
+    fn branch_cut_continuity_test() {
+        // Just off the cut the values agree with the signed-zero ones on it.
+        let eps = 1e-12;
+        for (on, near) in [
+            (c(-3.0, 0.0), c(-3.0, eps)),
+            (c(-3.0, -0.0), c(-3.0, -eps)),
+            (c(1.5, 0.0), c(1.5, eps)),
+            (c(1.5, -0.0), c(1.5, -eps)),
+        ] {
+            assert_close(on.sqrt(), near.sqrt(), 1e-11);
+            assert_close(on.ln(), near.ln(), 1e-11);
+            assert_close(on.asin(), near.asin(), 1e-6);
+            assert_close(on.acos(), near.acos(), 1e-6);
+            assert_close(on.acosh(), near.acosh(), 1e-6);
+            assert_close(on.atanh(), near.atanh(), 1e-11);
+        }
+        for (on, near) in [(c(0.0, 3.0), c(eps, 3.0)), (c(-0.0, -3.0), c(-eps, -3.0))] {
+            assert_close(on.atan(), near.atan(), 1e-11);
+            assert_close(on.asinh(), near.asinh(), 1e-11);
+        }
+    }
+
+    #[test]
+    fn large_arguments_test() {
+        assert_close(c(0.3, 800.0).tan(), c(0.0, 1.0), 1e-15);
+        assert_close(c(0.3, -800.0).tan(), c(0.0, -1.0), 1e-15);
+        assert_close(c(-900.0, 2.0).tanh(), c(-1.0, 0.0), 1e-15);
+        assert_close(c(-1e8, 0.0).asinh(), c(-(1e8f64.asinh()), 0.0), 1e-14);
+        let root = c(1e300, 1e300).sqrt();
+        assert_close(
+            c(root.re / 1e150, root.im / 1e150),
+            c(1.09868411346781, 0.455_089_860_562_227_33),
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn decimal_test() {
+        let d = |re: Decimal, im: Decimal| Complex { re, im };
+        let close = |a: Complex<Decimal>, b: Complex<Decimal>| {
+            (a.re - b.re).abs() < dec!(1e-24) && (a.im - b.im).abs() < dec!(1e-24)
+        };
+        assert_eq!(d(dec!(-4), dec!(0)).sqrt(), d(dec!(0), dec!(2)));
+        assert!(close(d(dec!(0), Decimal::PI).exp(), d(dec!(-1), dec!(0))));
+        assert!(close(d(dec!(-1), dec!(0)).ln(), d(dec!(0), Decimal::PI)));
+        let z = d(dec!(0.5), dec!(-1.25));
+        assert!(close(z.ln().exp(), z));
+        assert!(close(z.asin().sin(), z));
+        assert!(close(z.atanh().tanh(), z));
+        // sqrt(2i) = 1 + i
+        assert!(close(d(dec!(0), dec!(2)).sqrt(), d(dec!(1), dec!(1))));
+    }
+}
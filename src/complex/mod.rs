@@ -0,0 +1,412 @@
+mod elementary;
+mod ops;
+mod polar;
+mod real;
+
+pub use real::Real;
+
+use num::{FromPrimitive, Num};
+use std::ops::Neg;
+
+/// A complex number `re + im·i`. It is `Copy` whenever `T` is, so operands of the
+/// `std::ops` operators remain usable afterwards.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ComplexError {
+    DivisionByZero,
+}
+
+impl<T> Complex<T>
+where
+    T: Num + FromPrimitive + Copy,
+{
+    #[inline]
+    pub fn add(&self, operand: &Complex<T>) -> Complex<T> {
+        Complex {
+            re: self.re + operand.re,
+            im: self.im + operand.im,
+        }
+    }
+
+    #[inline]
+    pub fn substract(&self, operand: &Complex<T>) -> Complex<T> {
+        Complex {
+            re: self.re - operand.re,
+            im: self.im - operand.im,
+        }
+    }
+
+    #[inline]
+    pub fn multiply(&self, operand: &Complex<T>) -> Complex<T> {
+        Complex {
+            re: self.re * operand.re - self.im * operand.im,
+            im: self.im * operand.re + self.re * operand.im,
+        }
+    }
+
+    /// `self / operand` by the exact formula `self·conj(operand) / |operand|²`, which
+    /// truncates like integer division for integer components. `|operand|²` can overflow or
+    /// underflow for floats with large or small components; [`Complex::robust_divide`]
+    /// avoids forming it.
+    ///
+    /// Dividing by zero gives infinities or NaN for floats and panics for `Decimal` and
+    /// integers; [`Complex::checked_div`] reports it instead.
+    #[inline]
+    pub fn divide(&self, operand: &Complex<T>) -> Complex<T> {
+        let divisor = operand.norm_sqr();
+        Complex {
+            re: (self.re * operand.re + self.im * operand.im) / divisor,
+            im: (self.im * operand.re - self.re * operand.im) / divisor,
+        }
+    }
+
+    /// `|z|²`, which unlike [`Complex::norm`] needs no square root and is exact for integers.
+    #[inline]
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Reciprocal `1 / z`, with the same zero handling as [`Complex::divide`].
+    #[inline]
+    pub fn inv(&self) -> Complex<T> {
+        Complex {
+            re: T::one(),
+            im: T::zero(),
+        }
+        .divide(self)
+    }
+
+    /// [`Complex::divide`], or [`ComplexError::DivisionByZero`] for a zero divisor.
+    pub fn try_div(&self, operand: &Complex<T>) -> Result<Complex<T>, ComplexError> {
+        match operand.re.is_zero() && operand.im.is_zero() {
+            true => Err(ComplexError::DivisionByZero),
+            false => Ok(self.divide(operand)),
+        }
+    }
+
+    /// [`Complex::divide`], or `None` for a zero divisor.
+    pub fn checked_div(&self, operand: &Complex<T>) -> Option<Complex<T>> {
+        self.try_div(operand).ok()
+    }
+
+    /// [`Complex::inv`], or [`ComplexError::DivisionByZero`] for zero.
+    pub fn try_inv(&self) -> Result<Complex<T>, ComplexError> {
+        Complex {
+            re: T::one(),
+            im: T::zero(),
+        }
+        .try_div(self)
+    }
+
+    /// `z^n` by [`Complex::powi`].
+    #[inline]
+    pub fn pow(&self, n: i32) -> Complex<T> {
+        self.powi(i64::from(n))
+    }
+
+    /// `z^n` by exponentiation by squaring, in `O(log n)` multiplications. `z^0` is one,
+    /// including `0^0`.
+    pub fn powu(&self, n: u64) -> Complex<T> {
+        let mut result = Complex {
+            re: T::one(),
+            im: T::zero(),
+        };
+        let (mut base, mut n) = (*self, n);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.multiply(&base);
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.multiply(&base);
+            }
+        }
+        result
+    }
+
+    /// `z^n` for any sign of `n`; a negative exponent gives the reciprocal of `z^|n|`.
+    ///
+    /// A zero base with a negative exponent divides by zero: floats give infinities or NaN,
+    /// `Decimal` and integers panic. [`Complex::checked_powi`] reports it instead.
+    pub fn powi(&self, n: i64) -> Complex<T> {
+        let power = self.powu(n.unsigned_abs());
+        match n < 0 {
+            true => power.inv(),
+            false => power,
+        }
+    }
+
+    /// [`Complex::powi`], or `None` for a zero base with a negative exponent.
+    pub fn checked_powi(&self, n: i64) -> Option<Complex<T>> {
+        match n < 0 && self.re.is_zero() && self.im.is_zero() {
+            true => None,
+            false => Some(self.powi(n)),
+        }
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Num + FromPrimitive + Copy + Neg<Output = T>,
+{
+    /// Complex conjugate `re - im·i`. The imaginary part is negated rather than subtracted from
+    /// zero, so that `conj` moves a point on a branch cut to the other side of it.
+    #[inline]
+    pub fn conj(&self) -> Complex<T> {
+        Complex {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+}
+
+impl<T: Real> Complex<T> {
+    /// `self / operand` by Smith's algorithm, which scales by the larger divisor component so
+    /// that `|operand|²` is never formed and cannot overflow or underflow. Division by zero
+    /// behaves as in [`Complex::divide`].
+    pub fn robust_divide(&self, operand: &Complex<T>) -> Complex<T> {
+        let (a, b, c, d) = (self.re, self.im, operand.re, operand.im);
+        match c.abs() >= d.abs() {
+            true => {
+                let ratio = d / c;
+                let divisor = c + d * ratio;
+                Complex {
+                    re: (a + b * ratio) / divisor,
+                    im: (b - a * ratio) / divisor,
+                }
+            }
+            false => {
+                let ratio = c / d;
+                let divisor = c * ratio + d;
+                Complex {
+                    re: (a * ratio + b) / divisor,
+                    im: (b * ratio - a) / divisor,
+                }
+            }
+        }
+    }
+}
+
+//  TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn add_test() {
+        let cnum = Complex { re: 5.0, im: 7.0 };
+        let op = Complex { re: 7.0, im: 42.0 };
+        let res = cnum.add(&op);
+        assert_eq!(res, Complex { re: 12.0, im: 49.0 });
+    }
+
+    #[test]
+    fn substract_test() {
+        let cnum = Complex { re: 5.0, im: 7.0 };
+        let op = Complex { re: 7.0, im: 42.0 };
+        let res = cnum.substract(&op);
+        assert_eq!(
+            res,
+            Complex {
+                re: -2.0,
+                im: -35.0
+            }
+        );
+    }
+
+    #[test]
+    fn multiply_test() {
+        let cnum = Complex { re: 5.0, im: 7.0 };
+        let op = Complex { re: 7.0, im: 42.0 };
+        let res = cnum.multiply(&op);
+        assert_eq!(
+            res,
+            Complex {
+                re: -259.0,
+                im: 259.0
+            }
+        );
+    }
+
+    #[test]
+    fn divide_test() {
+        let cnum = Complex { re: 10.0, im: 10.0 };
+        let op = Complex { re: 5.0, im: 5.0 };
+        let res = cnum.divide(&op);
+        assert_eq!(res, Complex { re: 2.0, im: 0.0 });
+    }
+
+    #[test]
+    fn pow_test() {
+        let cnum = Complex { re: 10.0, im: 10.0 };
+        let res = cnum.pow(2);
+        let comp = cnum.multiply(&cnum.clone());
+        assert_eq!(res, comp);
+    }
+
+    #[test]
+    fn pow_zero_test() {
+        let one = Complex { re: 1.0, im: 0.0 };
+        assert_eq!(Complex { re: 3.0, im: -2.0 }.pow(0), one);
+        assert_eq!(Complex { re: 0.0, im: 0.0 }.pow(0), one);
+        assert_eq!(Complex { re: 0, im: 0 }.powu(0), Complex { re: 1, im: 0 });
+    }
+
+    #[test]
+    fn pow_negative_test() {
+        let z = Complex { re: 0.0, im: 2.0 };
+        assert_eq!(z.pow(-1), Complex { re: 0.0, im: -0.5 });
+        assert_eq!(z.pow(-2), Complex { re: -0.25, im: 0.0 });
+        let w = Complex { re: 1.0, im: 1.0 };
+        assert_eq!(w.powi(-4), Complex { re: -0.25, im: 0.0 });
+    }
+
+    #[test]
+    fn pow_large_exponent_test() {
+        let i = Complex { re: 0, im: 1 };
+        assert_eq!(i.powu(1_000_000_001), i);
+        assert_eq!(i.powi(i64::MIN), Complex { re: 1, im: 0 });
+        assert_eq!(Complex { re: 1, im: 1 }.powu(10), Complex { re: 0, im: 32 });
+        assert_eq!(
+            Complex { re: 1.0, im: 0.0 }.pow(i32::MIN),
+            Complex { re: 1.0, im: 0.0 }
+        );
+    }
+
+    #[test]
+    fn pow_matches_repeated_multiply_test() {
+        let z = Complex { re: 2, im: -3 };
+        let mut expected = Complex { re: 1, im: 0 };
+        for n in 0..12 {
+            assert_eq!(z.powu(n), expected);
+            expected = expected.multiply(&z);
+        }
+    }
+
+    #[test]
+    fn checked_pow_test() {
+        let zero = Complex { re: 0.0, im: 0.0 };
+        assert_eq!(zero.checked_powi(-1), None);
+        assert_eq!(zero.checked_powi(3), Some(zero));
+        assert_eq!(
+            Complex { re: 0.0, im: 2.0 }.checked_powi(-1),
+            Some(Complex { re: 0.0, im: -0.5 })
+        );
+        let decimal = Complex {
+            re: Decimal::ZERO,
+            im: Decimal::ZERO,
+        };
+        assert_eq!(decimal.checked_powi(-2), None);
+    }
+
+    #[test]
+    fn conj_test() {
+        assert_eq!(
+            Complex { re: 1.5, im: -2.0 }.conj(),
+            Complex { re: 1.5, im: 2.0 }
+        );
+        assert_eq!(Complex { re: 3, im: 4 }.conj(), Complex { re: 3, im: -4 });
+        let upper = Complex {
+            re: -1.0f64,
+            im: 0.0,
+        }
+        .conj();
+        assert!(upper.im == 0.0 && upper.im.is_sign_negative());
+        assert!(upper.conj().im.is_sign_positive());
+    }
+
+    #[test]
+    fn inv_test() {
+        assert_eq!(
+            Complex { re: 3.0, im: 4.0 }.inv(),
+            Complex {
+                re: 0.12,
+                im: -0.16
+            }
+        );
+        assert_eq!(
+            Complex { re: 0.0, im: 2.0 }.inv(),
+            Complex { re: 0.0, im: -0.5 }
+        );
+        assert_eq!(
+            Complex { re: 0.0, im: 0.0 }.try_inv(),
+            Err(ComplexError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn checked_div_test() {
+        let z = Complex { re: 1.0, im: 1.0 };
+        assert_eq!(z.checked_div(&Complex { re: 0.0, im: 0.0 }), None);
+        assert_eq!(
+            z.checked_div(&Complex { re: 0.0, im: 1.0 }),
+            Some(Complex { re: 1.0, im: -1.0 })
+        );
+        assert_eq!(
+            z.try_div(&Complex { re: 0.0, im: -0.0 }),
+            Err(ComplexError::DivisionByZero)
+        );
+        let decimal = Complex {
+            re: Decimal::ONE,
+            im: Decimal::ONE,
+        };
+        assert_eq!(
+            decimal.checked_div(&Complex {
+                re: Decimal::ZERO,
+                im: Decimal::ZERO
+            }),
+            None
+        );
+        assert_eq!(
+            Complex { re: 7, im: 1 }.checked_div(&Complex { re: 0, im: 0 }),
+            None
+        );
+    }
+
+    #[test]
+    fn robust_divide_test() {
+        // |operand|² overflows f64 (1e600) and underflows it (1e-600).
+        let big = Complex {
+            re: 3e300,
+            im: 4e300,
+        };
+        assert_eq!(big.robust_divide(&big), Complex { re: 1.0, im: 0.0 });
+        let small = Complex {
+            re: 3e-300,
+            im: -4e-300,
+        };
+        let q = Complex { re: 1.0, im: 2.0 }.robust_divide(&small);
+        assert!((q.re / -2e299 - 1.0).abs() < 1e-15 && (q.im / 4e299 - 1.0).abs() < 1e-15);
+        // Squaring 1e15 overflows Decimal.
+        let d = Complex {
+            re: Decimal::from(1_000_000_000_000_000i64),
+            im: Decimal::from(1_000_000_000_000_000i64),
+        };
+        assert_eq!(
+            d.robust_divide(&d),
+            Complex {
+                re: Decimal::ONE,
+                im: Decimal::ZERO
+            }
+        );
+    }
+
+    #[test]
+    fn integer_divide_test() {
+        // (7 + i)(3 - i)/10 = (22 - 4i)/10, truncated.
+        assert_eq!(
+            Complex { re: 7, im: 1 }.divide(&Complex { re: 3, im: 1 }),
+            Complex { re: 2, im: 0 }
+        );
+        assert_eq!(
+            Complex { re: 10, im: 20 }.divide(&Complex { re: 0, im: 5 }),
+            Complex { re: 4, im: -2 }
+        );
+    }
+}
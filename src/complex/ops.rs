@@ -0,0 +1,332 @@
+//! `std::ops` implementations. Every operator forwards to the named method of the same
+//! arithmetic (`+` to [`Complex::add`], `/` to [`Complex::divide`], ...), so both spellings
+//! always agree.
+
+use super::Complex;
+use num::{FromPrimitive, Num};
+use rust_decimal::Decimal;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// `Complex ∘ Complex` for every combination of owned and borrowed operands, plus the
+/// matching `∘=` for owned and borrowed right-hand sides.
+macro_rules! complex_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $method:ident) => {
+        impl<'a, 'b, T: Num + FromPrimitive + Copy> $Op<&'b Complex<T>> for &'a Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: &'b Complex<T>) -> Complex<T> {
+                Complex::$method(self, rhs)
+            }
+        }
+
+        impl<'a, T: Num + FromPrimitive + Copy> $Op<&'a Complex<T>> for Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: &'a Complex<T>) -> Complex<T> {
+                Complex::$method(&self, rhs)
+            }
+        }
+
+        impl<'a, T: Num + FromPrimitive + Copy> $Op<Complex<T>> for &'a Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: Complex<T>) -> Complex<T> {
+                Complex::$method(self, &rhs)
+            }
+        }
+
+        impl<T: Num + FromPrimitive + Copy> $Op<Complex<T>> for Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: Complex<T>) -> Complex<T> {
+                Complex::$method(&self, &rhs)
+            }
+        }
+
+        impl<'a, T: Num + FromPrimitive + Copy> $OpAssign<&'a Complex<T>> for Complex<T> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: &'a Complex<T>) {
+                *self = Complex::$method(self, rhs);
+            }
+        }
+
+        impl<T: Num + FromPrimitive + Copy> $OpAssign<Complex<T>> for Complex<T> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Complex<T>) {
+                *self = Complex::$method(self, &rhs);
+            }
+        }
+    };
+}
+
+complex_op!(Add, add, AddAssign, add_assign, add);
+complex_op!(Sub, sub, SubAssign, sub_assign, substract);
+complex_op!(Mul, mul, MulAssign, mul_assign, multiply);
+complex_op!(Div, div, DivAssign, div_assign, divide);
+
+/// `Complex ∘ T` for owned and borrowed complex operands, and `Complex ∘= T`. The scalar is
+/// promoted to `T + 0i`, so the result matches the complex-complex operator exactly.
+macro_rules! scalar_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $method:ident) => {
+        impl<'a, T: Num + FromPrimitive + Copy> $Op<T> for &'a Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: T) -> Complex<T> {
+                Complex::$method(self, &real(rhs))
+            }
+        }
+
+        impl<T: Num + FromPrimitive + Copy> $Op<T> for Complex<T> {
+            type Output = Complex<T>;
+
+            #[inline]
+            fn $op(self, rhs: T) -> Complex<T> {
+                Complex::$method(&self, &real(rhs))
+            }
+        }
+
+        impl<T: Num + FromPrimitive + Copy> $OpAssign<T> for Complex<T> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: T) {
+                *self = Complex::$method(self, &real(rhs));
+            }
+        }
+    };
+}
+
+scalar_op!(Add, add, AddAssign, add_assign, add);
+scalar_op!(Sub, sub, SubAssign, sub_assign, substract);
+scalar_op!(Mul, mul, MulAssign, mul_assign, multiply);
+scalar_op!(Div, div, DivAssign, div_assign, divide);
+
+/// `T ∘ Complex<T>`. The orphan rule rules out a blanket impl over `T`, so these are
+/// spelled out for the element types the crate is used with.
+macro_rules! left_scalar_ops {
+    ($($T:ty),*) => {$(
+        left_scalar_ops!(@op $T, Add, add, add);
+        left_scalar_ops!(@op $T, Sub, sub, substract);
+        left_scalar_ops!(@op $T, Mul, mul, multiply);
+        left_scalar_ops!(@op $T, Div, div, divide);
+    )*};
+    (@op $T:ty, $Op:ident, $op:ident, $method:ident) => {
+        impl<'a> $Op<&'a Complex<$T>> for $T {
+            type Output = Complex<$T>;
+
+            #[inline]
+            fn $op(self, rhs: &'a Complex<$T>) -> Complex<$T> {
+                Complex::$method(&real(self), rhs)
+            }
+        }
+
+        impl $Op<Complex<$T>> for $T {
+            type Output = Complex<$T>;
+
+            #[inline]
+            fn $op(self, rhs: Complex<$T>) -> Complex<$T> {
+                Complex::$method(&real(self), &rhs)
+            }
+        }
+    };
+}
+
+left_scalar_ops!(f32, f64, i32, i64, Decimal);
+
+impl<T: Num + FromPrimitive + Copy + Neg<Output = T>> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    #[inline]
+    fn neg(self) -> Complex<T> {
+        -&self
+    }
+}
+
+/// Negates each component, so signed zeros flip instead of collapsing to `+0.0`.
+impl<T: Num + FromPrimitive + Copy + Neg<Output = T>> Neg for &Complex<T> {
+    type Output = Complex<T>;
+
+    #[inline]
+    fn neg(self) -> Complex<T> {
+        Complex {
+            re: -self.re,
+            im: -self.im,
+        }
+    }
+}
+
+impl<T: Num + FromPrimitive + Copy> Sum for Complex<T> {
+    fn sum<I: Iterator<Item = Complex<T>>>(iter: I) -> Self {
+        iter.fold(real(T::zero()), |acc, c| acc + c)
+    }
+}
+
+impl<'a, T: Num + FromPrimitive + Copy + 'a> Sum<&'a Complex<T>> for Complex<T> {
+    fn sum<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Self {
+        iter.fold(real(T::zero()), |acc, c| acc + c)
+    }
+}
+
+impl<T: Num + FromPrimitive + Copy> Product for Complex<T> {
+    fn product<I: Iterator<Item = Complex<T>>>(iter: I) -> Self {
+        iter.fold(real(T::one()), |acc, c| acc * c)
+    }
+}
+
+impl<'a, T: Num + FromPrimitive + Copy + 'a> Product<&'a Complex<T>> for Complex<T> {
+    fn product<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Self {
+        iter.fold(real(T::one()), |acc, c| acc * c)
+    }
+}
+
+#[inline]
+fn real<T: Num>(re: T) -> Complex<T> {
+    Complex { re, im: T::zero() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex { re, im }
+    }
+
+    #[test]
+    fn binary_ops_match_methods_test() {
+        let (a, b) = (c(5.0, 7.0), c(7.0, 42.0));
+        assert_eq!(a + b, a.add(&b));
+        assert_eq!(a - b, a.substract(&b));
+        assert_eq!(a * b, a.multiply(&b));
+        assert_eq!(a / b, a.divide(&b));
+        assert_eq!(a * b, c(-259.0, 259.0));
+        assert_eq!(a + b, c(12.0, 49.0));
+        assert_eq!(a - b, c(-2.0, -35.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn borrowed_operands_test() {
+        let (a, b) = (c(5.0, 7.0), c(7.0, 42.0));
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(a - &b, a - b);
+        assert_eq!(&a * b, a * b);
+        assert_eq!(&a / &b, a / b);
+        assert_eq!(&a * 2.0, a * 2.0);
+        assert_eq!(2.0 - &a, 2.0 - a);
+        assert_eq!(-&a, -a);
+    }
+
+    #[test]
+    fn readable_formula_test() {
+        let (a, b, d) = (c(1.0, 2.0), c(3.0, -1.0), c(0.5, 0.5));
+        assert_eq!(a * b + d, a.multiply(&b).add(&d));
+        assert_eq!(
+            -(a - b) / d,
+            c(0.0, 0.0).substract(&a.substract(&b)).divide(&d)
+        );
+    }
+
+    #[test]
+    fn assign_ops_test() {
+        let mut z = c(1.0, 1.0);
+        z += c(1.0, 2.0);
+        assert_eq!(z, c(2.0, 3.0));
+        z -= &c(0.5, 0.5);
+        assert_eq!(z, c(1.5, 2.5));
+        z *= c(0.0, 2.0);
+        assert_eq!(z, c(-5.0, 3.0));
+        z /= &c(0.0, 2.0);
+        assert_eq!(z, c(1.5, 2.5));
+        z *= 2.0;
+        z += 1.0;
+        z -= 0.5;
+        z /= 5.0;
+        assert_eq!(z, c(0.7, 1.0));
+    }
+
+    #[test]
+    fn scalar_ops_test() {
+        let z = c(3.0, -4.0);
+        assert_eq!(z * 2.0, c(6.0, -8.0));
+        assert_eq!(z / 2.0, c(1.5, -2.0));
+        assert_eq!(z + 1.0, c(4.0, -4.0));
+        assert_eq!(z - 1.0, c(2.0, -4.0));
+        assert_eq!(2.0 * z, c(6.0, -8.0));
+        assert_eq!(1.0 + z, c(4.0, -4.0));
+        assert_eq!(1.0 - z, c(-2.0, 4.0));
+        assert_eq!(25.0 / z, c(3.0, 4.0));
+        assert_eq!(
+            2.0f32
+                * Complex {
+                    re: 1.0f32,
+                    im: 1.0
+                },
+            Complex { re: 2.0, im: 2.0 }
+        );
+        assert_eq!(3 - Complex { re: 1, im: 1 }, Complex { re: 2, im: -1 });
+    }
+
+    #[test]
+    fn decimal_ops_test() {
+        let z = Complex {
+            re: dec!(1.5),
+            im: dec!(-2),
+        };
+        assert_eq!(
+            dec!(2) * z,
+            Complex {
+                re: dec!(3.0),
+                im: dec!(-4)
+            }
+        );
+        assert_eq!(
+            z + dec!(0.5),
+            Complex {
+                re: dec!(2.0),
+                im: dec!(-2)
+            }
+        );
+        assert_eq!(
+            -z,
+            Complex {
+                re: dec!(-1.5),
+                im: dec!(2)
+            }
+        );
+    }
+
+    #[test]
+    fn neg_test() {
+        assert_eq!(-c(1.0, -2.0), c(-1.0, 2.0));
+        assert_eq!(-&c(0.0, 3.0), c(0.0, -3.0));
+        let zero = -c(0.0, 0.0);
+        assert!(zero.re.is_sign_negative() && zero.im.is_sign_negative());
+        assert!((-zero).re.is_sign_positive() && (-zero).im.is_sign_positive());
+    }
+
+    #[test]
+    fn sum_product_test() {
+        let values = vec![c(1.0, 1.0), c(2.0, -1.0), c(0.0, 3.0)];
+        assert_eq!(values.iter().sum::<Complex<f64>>(), c(3.0, 3.0));
+        assert_eq!(values.iter().product::<Complex<f64>>(), c(-3.0, 9.0));
+        assert_eq!(
+            values.clone().into_iter().sum::<Complex<f64>>(),
+            c(3.0, 3.0)
+        );
+        assert_eq!(values.into_iter().product::<Complex<f64>>(), c(-3.0, 9.0));
+        assert_eq!(
+            Vec::<Complex<f64>>::new().iter().sum::<Complex<f64>>(),
+            c(0.0, 0.0)
+        );
+        assert_eq!(
+            Vec::<Complex<f64>>::new().iter().product::<Complex<f64>>(),
+            c(1.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::{Complex, Real};
+
+impl<T: Real> Complex<T> {
+    /// Modulus `|z|`, computed with [`Real::hypot`] so it does not overflow where `|z|`
+    /// itself is representable.
+    pub fn norm(&self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    /// Argument of `z` in `(-π, π]`; the argument of zero is zero.
+    pub fn arg(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    /// `(|z|, arg z)`.
+    pub fn to_polar(&self) -> (T, T) {
+        (self.norm(), self.arg())
+    }
+
+    /// `r·e^(iθ)`.
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Complex {
+            re: r * theta.cos(),
+            im: r * theta.sin(),
+        }
+    }
+
+    /// `e^(iθ) = cos θ + i·sin θ`.
+    pub fn cis(theta: T) -> Self {
+        Self::from_polar(T::one(), theta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex { re, im }
+    }
+
+    #[test]
+    fn norm_test() {
+        assert_eq!(c(3.0, 4.0).norm(), 5.0);
+        assert_eq!(c(-3.0, 4.0).norm_sqr(), 25.0);
+        assert!((c(3e200, 4e200).norm() / 5e200 - 1.0).abs() < 1e-15);
+        assert_eq!(
+            Complex {
+                re: 3.0f32,
+                im: -4.0
+            }
+            .norm(),
+            5.0
+        );
+        assert_eq!(
+            Complex {
+                re: dec!(-6e27),
+                im: dec!(8e27)
+            }
+            .norm(),
+            dec!(1e28)
+        );
+    }
+
+    #[test]
+    fn arg_test() {
+        assert_eq!(c(1.0, 0.0).arg(), 0.0);
+        assert_eq!(c(0.0, 2.0).arg(), FRAC_PI_2);
+        assert_eq!(c(-1.0, 0.0).arg(), PI);
+        assert_eq!(c(-1.0, -0.0).arg(), -PI);
+        assert_eq!(c(1.0, -1.0).arg(), -FRAC_PI_4);
+        assert_eq!(c(0.0, 0.0).arg(), 0.0);
+        assert_eq!(
+            Complex {
+                re: dec!(-1),
+                im: dec!(0)
+            }
+            .arg(),
+            Decimal::PI
+        );
+    }
+
+    #[test]
+    fn polar_round_trip_test() {
+        for z in [c(1.5, -2.0), c(-0.3, 0.7), c(-4.0, -1e-3), c(0.0, 9.0)] {
+            let (r, theta) = z.to_polar();
+            let w = Complex::from_polar(r, theta);
+            assert!(
+                (w.re - z.re).abs() < 1e-14 && (w.im - z.im).abs() < 1e-14,
+                "{:?}",
+                z
+            );
+        }
+
+        let z = Complex {
+            re: dec!(1.5),
+            im: dec!(-2),
+        };
+        let (r, theta) = z.to_polar();
+        assert_eq!(r, dec!(2.5));
+        let w = Complex::from_polar(r, theta);
+        assert!((w.re - z.re).abs() < dec!(1e-25) && (w.im - z.im).abs() < dec!(1e-25));
+    }
+
+    #[test]
+    fn cis_test() {
+        let z = Complex::cis(FRAC_PI_2);
+        assert!(z.re.abs() < 1e-16 && z.im == 1.0);
+        let z: Complex<f64> = Complex::cis(PI / 3.0);
+        assert!((z.norm() - 1.0).abs() < 1e-15 && (z.arg() - PI / 3.0).abs() < 1e-15);
+        let z = Complex::cis(Decimal::PI);
+        assert!((z.re + Decimal::ONE).abs() < dec!(1e-26) && z.im.abs() < dec!(1e-26));
+    }
+}
@@ -0,0 +1,318 @@
+use num::{FromPrimitive, Num};
+use rust_decimal::{Decimal, MathematicalOps};
+use std::ops::Neg;
+
+/// Real element types with the transcendental functions the polar and elementary complex
+/// functions are built from: `f32` and `f64` through `std`, `Decimal` through its
+/// [`MathematicalOps`].
+pub trait Real: Num + FromPrimitive + Copy + PartialOrd + Neg<Output = Self> {
+    fn abs(self) -> Self;
+
+    /// Square root of a non-negative value.
+    fn sqrt(self) -> Self;
+
+    fn exp(self) -> Self;
+
+    /// Natural logarithm of a positive value.
+    fn ln(self) -> Self;
+
+    fn sin(self) -> Self;
+
+    fn cos(self) -> Self;
+
+    fn sinh(self) -> Self;
+
+    fn cosh(self) -> Self;
+
+    /// Angle of the point `(other, self)` in `(-π, π]`.
+    fn atan2(self, other: Self) -> Self;
+
+    /// Whether the sign bit is set, which for floats includes `-0.0`.
+    fn is_sign_negative(self) -> bool;
+
+    fn pi() -> Self;
+
+    /// `√(self² + other²)` without intermediate overflow or underflow: the smaller operand
+    /// is scaled by the larger one before squaring.
+    fn hypot(self, other: Self) -> Self {
+        let (a, b) = (self.abs(), other.abs());
+        let (large, small) = match a >= b {
+            true => (a, b),
+            false => (b, a),
+        };
+        if large.is_zero() {
+            return large;
+        }
+        let ratio = small / large;
+        large * (Self::one() + ratio * ratio).sqrt()
+    }
+}
+
+macro_rules! float_real {
+    ($($T:ident),*) => {$(
+        impl Real for $T {
+            #[inline]
+            fn abs(self) -> Self {
+                <$T>::abs(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$T>::sqrt(self)
+            }
+
+            #[inline]
+            fn exp(self) -> Self {
+                <$T>::exp(self)
+            }
+
+            #[inline]
+            fn ln(self) -> Self {
+                <$T>::ln(self)
+            }
+
+            #[inline]
+            fn sin(self) -> Self {
+                <$T>::sin(self)
+            }
+
+            #[inline]
+            fn cos(self) -> Self {
+                <$T>::cos(self)
+            }
+
+            #[inline]
+            fn sinh(self) -> Self {
+                <$T>::sinh(self)
+            }
+
+            #[inline]
+            fn cosh(self) -> Self {
+                <$T>::cosh(self)
+            }
+
+            #[inline]
+            fn atan2(self, other: Self) -> Self {
+                <$T>::atan2(self, other)
+            }
+
+            #[inline]
+            fn is_sign_negative(self) -> bool {
+                <$T>::is_sign_negative(self)
+            }
+
+            #[inline]
+            fn pi() -> Self {
+                std::$T::consts::PI
+            }
+
+            #[inline]
+            fn hypot(self, other: Self) -> Self {
+                <$T>::hypot(self, other)
+            }
+        }
+    )*};
+}
+
+float_real!(f32, f64);
+
+impl Real for Decimal {
+    fn abs(self) -> Self {
+        Decimal::abs(&self)
+    }
+
+    /// # Panics
+    ///
+    /// On negative input.
+    fn sqrt(self) -> Self {
+        MathematicalOps::sqrt(&self).expect("square root of a negative Decimal")
+    }
+
+    /// Values whose exponential is below `Decimal`'s resolution give zero.
+    ///
+    /// # Panics
+    ///
+    /// If the result exceeds the `Decimal` range, i.e. above about `66.5`.
+    fn exp(self) -> Self {
+        match self.checked_exp() {
+            Some(value) => value,
+            None if self.is_sign_negative() => Decimal::ZERO,
+            None => panic!("exponential overflowed Decimal"),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// On zero or negative input.
+    fn ln(self) -> Self {
+        self.checked_ln()
+            .expect("logarithm of a non-positive Decimal")
+    }
+
+    fn sin(self) -> Self {
+        MathematicalOps::sin(&self)
+    }
+
+    fn cos(self) -> Self {
+        MathematicalOps::cos(&self)
+    }
+
+    fn sinh(self) -> Self {
+        // (eˣ - e⁻ˣ)/2 cancels for small x, where the series converges quickly instead.
+        if self.abs() >= Decimal::ONE {
+            return (Real::exp(self) - Real::exp(-self)) / Decimal::TWO;
+        }
+        let square = self * self;
+        let (mut sum, mut term) = (self, self);
+        let mut k = 1u32;
+        while !term.is_zero() {
+            term = term * square / Decimal::from((2 * k) * (2 * k + 1));
+            sum += term;
+            k += 1;
+        }
+        sum
+    }
+
+    fn cosh(self) -> Self {
+        (Real::exp(self) + Real::exp(-self)) / Decimal::TWO
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self, other);
+        if x.is_zero() {
+            return match y.cmp(&Decimal::ZERO) {
+                std::cmp::Ordering::Greater => Decimal::HALF_PI,
+                std::cmp::Ordering::Less => -Decimal::HALF_PI,
+                std::cmp::Ordering::Equal => Decimal::ZERO,
+            };
+        }
+
+        // atan(y/x) on the quotient is fine while |y| ≤ |x|; otherwise use the complement so
+        // the quotient never exceeds one and cannot overflow.
+        let base = match y.abs() <= x.abs() {
+            true => atan(y / x),
+            false => {
+                let complement = Decimal::HALF_PI - atan((x / y).abs());
+                match (y / x).is_sign_negative() {
+                    true => -complement,
+                    false => complement,
+                }
+            }
+        };
+        match (x.is_sign_negative(), y.is_sign_negative()) {
+            (false, _) => base,
+            (true, false) => base + Decimal::PI,
+            (true, true) => base - Decimal::PI,
+        }
+    }
+
+    fn is_sign_negative(self) -> bool {
+        Decimal::is_sign_negative(&self)
+    }
+
+    fn pi() -> Self {
+        Decimal::PI
+    }
+}
+
+/// Arctangent of `|x| ≤ 1`, halving the argument twice with
+/// `atan(x) = 2·atan(x / (1 + √(1 + x²)))` before summing the Maclaurin series.
+fn atan(x: Decimal) -> Decimal {
+    let mut x = x;
+    for _ in 0..2 {
+        x /= Decimal::ONE + (Decimal::ONE + x * x).sqrt();
+    }
+
+    let square = x * x;
+    let (mut sum, mut power) = (x, x);
+    let mut k = 1i64;
+    loop {
+        power = -power * square;
+        let term = power / Decimal::from(2 * k + 1);
+        if term.is_zero() {
+            break;
+        }
+        sum += term;
+        k += 1;
+    }
+    sum * Decimal::from(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn hypot_test() {
+        assert_eq!(Real::hypot(3.0f64, -4.0), 5.0);
+        assert_eq!(Real::hypot(3e300f64, 4e300), 5e300);
+        assert_eq!(Real::hypot(3e-300f64, 4e-300), 5e-300);
+        assert_eq!(Real::hypot(3e30f32, 4e30), 5e30);
+        assert_eq!(Real::hypot(dec!(3), dec!(-4)), dec!(5));
+        assert_eq!(Real::hypot(Decimal::ZERO, Decimal::ZERO), Decimal::ZERO);
+        // Squaring 6e27 would overflow Decimal's range of about 7.9e28.
+        assert_eq!(Real::hypot(dec!(6e27), dec!(8e27)), dec!(1e28));
+    }
+
+    #[test]
+    fn decimal_atan2_test() {
+        // Reference angles to 28 digits.
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < dec!(1e-26);
+        assert!(close(dec!(1).atan2(dec!(1)), Decimal::QUARTER_PI));
+        assert!(close(
+            dec!(1).atan2(dec!(-1)),
+            Decimal::PI - Decimal::QUARTER_PI
+        ));
+        assert!(close(
+            dec!(-1).atan2(dec!(-1)),
+            Decimal::QUARTER_PI - Decimal::PI
+        ));
+        assert!(close(
+            dec!(2).atan2(dec!(1)),
+            dec!(1.107148717794090503017065460)
+        ));
+        assert!(close(
+            dec!(-0.5).atan2(dec!(3)),
+            dec!(-0.1651486774146268382791282896)
+        ));
+        assert!(close(dec!(1e-20).atan2(dec!(1)), dec!(1e-20)));
+        assert_eq!(dec!(3).atan2(dec!(0)), Decimal::HALF_PI);
+        assert_eq!(dec!(-3).atan2(dec!(0)), -Decimal::HALF_PI);
+        assert_eq!(dec!(0).atan2(dec!(-2)), Decimal::PI);
+        assert_eq!(dec!(0).atan2(dec!(0)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn decimal_hyperbolic_test() {
+        // References to 28 digits.
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < dec!(1e-26);
+        assert!(close(
+            dec!(0.5).sinh(),
+            dec!(0.5210953054937473616224256264)
+        ));
+        assert!(close(
+            dec!(-0.1).sinh(),
+            dec!(-0.1001667500198440258237293835)
+        ));
+        assert!(close(dec!(2).sinh(), dec!(3.626860407847018767668213982)));
+        assert!(close(dec!(-3).cosh(), dec!(10.06766199577776584195393603)));
+        assert_eq!(Real::exp(dec!(-100)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn decimal_atan2_matches_f64_test() {
+        for (y, x) in [
+            (0.3, 0.7),
+            (-2.0, 0.1),
+            (5.0, -3.0),
+            (-0.01, -4.0),
+            (7.0, 7.5),
+        ] {
+            let d = Decimal::from_f64(y)
+                .unwrap()
+                .atan2(Decimal::from_f64(x).unwrap());
+            assert!((d.to_string().parse::<f64>().unwrap() - f64::atan2(y, x)).abs() < 1e-15);
+        }
+    }
+}
@@ -0,0 +1,393 @@
+mod streaming;
+
+pub use streaming::{BlockMethod, StreamingConvolver};
+
+use crate::complex::Complex;
+use crate::fft::{fft_in_place, ifft_in_place, irfft, rfft, zero, FftNum};
+
+/// When the shorter operand has at most this many samples the sums are evaluated directly,
+/// otherwise both operands are zero-padded and multiplied in the frequency domain.
+const DIRECT_MAX_LEN: usize = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum ConvolutionError {
+    EmptyKernel,
+    FftTooShort,
+}
+
+/// Which part of the result [`convolve`] and [`correlate`] return.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolutionMode {
+    /// The whole linear result, `a.len() + b.len() - 1` samples.
+    #[default]
+    Full,
+    /// The centre of the linear result, as long as the longer operand.
+    Same,
+    /// Only the samples where the operands overlap completely.
+    Valid,
+    /// Circular result over `max(a.len(), b.len())` samples, the shorter operand being
+    /// zero-padded first.
+    Circular,
+}
+
+/// Linear or circular convolution of two real sequences. Long operands go through the FFT
+/// in floating point; [`crate::ntt::multiply_exact`] convolves integers exactly.
+pub fn convolve<T: FftNum>(a: &[T], b: &[T], mode: ConvolutionMode) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    match mode {
+        ConvolutionMode::Circular => circular_real(a, b),
+        _ => trim(linear_real(a, b), a.len(), b.len(), mode),
+    }
+}
+
+/// Cross-correlation `c[k] = Σ a[n + k] · b[n]`. Linear modes order the lags from
+/// `-(b.len() - 1)` to `a.len() - 1`; the circular mode starts at lag zero.
+pub fn correlate<T: FftNum>(a: &[T], b: &[T], mode: ConvolutionMode) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    match mode {
+        ConvolutionMode::Circular => {
+            let length = a.len().max(b.len());
+            let flipped: Vec<T> = (0..length)
+                .map(|m| b.get((length - m) % length).copied().unwrap_or(T::zero()))
+                .collect();
+            circular_real(a, &flipped)
+        }
+        _ => {
+            let flipped: Vec<T> = b.iter().rev().copied().collect();
+            trim(linear_real(a, &flipped), a.len(), b.len(), mode)
+        }
+    }
+}
+
+pub fn convolve_complex<T: FftNum>(
+    a: &[Complex<T>],
+    b: &[Complex<T>],
+    mode: ConvolutionMode,
+) -> Vec<Complex<T>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    match mode {
+        ConvolutionMode::Circular => circular_complex(a, b),
+        _ => trim(linear_complex(a, b), a.len(), b.len(), mode),
+    }
+}
+
+/// Complex cross-correlation `c[k] = Σ a[n + k] · conj(b[n])`, with the lags ordered as in
+/// [`correlate`].
+pub fn correlate_complex<T: FftNum>(
+    a: &[Complex<T>],
+    b: &[Complex<T>],
+    mode: ConvolutionMode,
+) -> Vec<Complex<T>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    match mode {
+        ConvolutionMode::Circular => {
+            let length = a.len().max(b.len());
+            let flipped: Vec<Complex<T>> = (0..length)
+                .map(|m| {
+                    b.get((length - m) % length)
+                        .map_or_else(zero, Complex::conj)
+                })
+                .collect();
+            circular_complex(a, &flipped)
+        }
+        _ => {
+            let flipped: Vec<Complex<T>> = b.iter().rev().map(Complex::conj).collect();
+            trim(linear_complex(a, &flipped), a.len(), b.len(), mode)
+        }
+    }
+}
+
+fn linear_real<T: FftNum>(a: &[T], b: &[T]) -> Vec<T> {
+    let length = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= DIRECT_MAX_LEN {
+        let mut out = vec![T::zero(); length];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                out[i + j] = out[i + j] + x * y;
+            }
+        }
+        return out;
+    }
+
+    let padded = length.next_power_of_two();
+    let mut out = multiply_real_spectra(a, b, padded);
+    out.truncate(length);
+    out
+}
+
+fn circular_real<T: FftNum>(a: &[T], b: &[T]) -> Vec<T> {
+    let length = a.len().max(b.len());
+    if a.len().min(b.len()) <= DIRECT_MAX_LEN {
+        let mut out = vec![T::zero(); length];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                let k = (i + j) % length;
+                out[k] = out[k] + x * y;
+            }
+        }
+        return out;
+    }
+
+    multiply_real_spectra(a, b, length)
+}
+
+/// Circular convolution of `a` and `b` zero-padded to `length` through [`rfft`].
+fn multiply_real_spectra<T: FftNum>(a: &[T], b: &[T], length: usize) -> Vec<T> {
+    let pad = |x: &[T]| {
+        let mut padded = x.to_vec();
+        padded.resize(length, T::zero());
+        rfft(&padded)
+    };
+    let product: Vec<Complex<T>> = pad(a)
+        .iter()
+        .zip(pad(b).iter())
+        .map(|(x, y)| x.multiply(y))
+        .collect();
+    irfft(&product, length)
+}
+
+fn linear_complex<T: FftNum>(a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    let length = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= DIRECT_MAX_LEN {
+        let mut out = vec![zero(); length];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                out[i + j] = out[i + j].add(&x.multiply(y));
+            }
+        }
+        return out;
+    }
+
+    let padded = length.next_power_of_two();
+    let mut out = multiply_complex_spectra(a, b, padded);
+    out.truncate(length);
+    out
+}
+
+fn circular_complex<T: FftNum>(a: &[Complex<T>], b: &[Complex<T>]) -> Vec<Complex<T>> {
+    let length = a.len().max(b.len());
+    if a.len().min(b.len()) <= DIRECT_MAX_LEN {
+        let mut out = vec![zero(); length];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                let k = (i + j) % length;
+                out[k] = out[k].add(&x.multiply(y));
+            }
+        }
+        return out;
+    }
+
+    multiply_complex_spectra(a, b, length)
+}
+
+fn multiply_complex_spectra<T: FftNum>(
+    a: &[Complex<T>],
+    b: &[Complex<T>],
+    length: usize,
+) -> Vec<Complex<T>> {
+    let pad = |x: &[Complex<T>]| {
+        let mut padded = x.to_vec();
+        padded.resize(length, zero());
+        fft_in_place(&mut padded);
+        padded
+    };
+    let mut product: Vec<Complex<T>> = pad(a)
+        .iter()
+        .zip(pad(b).iter())
+        .map(|(x, y)| x.multiply(y))
+        .collect();
+    ifft_in_place(&mut product);
+    product
+}
+
+/// Cuts the `Same` or `Valid` part out of a full linear result.
+fn trim<S>(full: Vec<S>, a_len: usize, b_len: usize, mode: ConvolutionMode) -> Vec<S> {
+    let (longer, shorter) = (a_len.max(b_len), a_len.min(b_len));
+    let (start, length) = match mode {
+        ConvolutionMode::Same => ((full.len() - longer) / 2, longer),
+        ConvolutionMode::Valid => (shorter - 1, longer - shorter + 1),
+        ConvolutionMode::Full | ConvolutionMode::Circular => return full,
+    };
+    full.into_iter().skip(start).take(length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(length: usize, seed: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| ((i * 7 + seed) % 11) as f64 - 5.0 + 0.5 * ((i + seed) % 3) as f64)
+            .collect()
+    }
+
+    fn complex_signal(length: usize, seed: usize) -> Vec<Complex<f64>> {
+        signal(length, seed)
+            .into_iter()
+            .zip(signal(length, seed + 4))
+            .map(|(re, im)| Complex { re, im })
+            .collect()
+    }
+
+    fn reference(a: &[f64], b: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                out[i + j] += x * y;
+            }
+        }
+        out
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-8));
+    }
+
+    fn assert_close_complex(a: &[Complex<f64>], b: &[Complex<f64>]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x.re - y.re).abs() < 1e-8 && (x.im - y.im).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn convolve_small_test() {
+        let res = convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolutionMode::Full);
+        assert_eq!(res, vec![0.0, 1.0, 2.5, 4.0, 1.5]);
+        let same = convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolutionMode::Same);
+        assert_eq!(same, vec![1.0, 2.5, 4.0]);
+        let valid = convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolutionMode::Valid);
+        assert_eq!(valid, vec![2.5]);
+    }
+
+    #[test]
+    fn fft_path_matches_direct_test() {
+        for (la, lb) in [(100, 40), (33, 33), (257, 90), (64, 1000)] {
+            let (a, b) = (signal(la, 1), signal(lb, 2));
+            assert_close(&convolve(&a, &b, ConvolutionMode::Full), &reference(&a, &b));
+        }
+    }
+
+    #[test]
+    fn long_integer_valued_input_test() {
+        // Longer than DIRECT_MAX_LEN, so both go through the FFT path.
+        let a: Vec<f64> = (1..=40).map(f64::from).collect();
+        let ones = vec![1.0; 40];
+        let prefix_sums: Vec<f64> = (1..=40).map(|n| f64::from(n * (n + 1) / 2)).collect();
+        let res = convolve(&a, &ones, ConvolutionMode::Full);
+        assert_eq!(res.len(), 79);
+        assert_close(&res[..40], &prefix_sums);
+        // Lag k ≥ 0 sums a[k..], which is 820 - k(k + 1)/2.
+        let tails: Vec<f64> = (0..40).map(|k| f64::from(820 - k * (k + 1) / 2)).collect();
+        assert_close(&correlate(&a, &ones, ConvolutionMode::Full)[39..], &tails);
+    }
+
+    #[test]
+    fn same_and_valid_lengths_test() {
+        let (a, b) = (signal(120, 3), signal(50, 4));
+        let full = reference(&a, &b);
+        let same = convolve(&a, &b, ConvolutionMode::Same);
+        assert_eq!(same.len(), 120);
+        assert_close(&same, &full[24..144]);
+        let valid = convolve(&a, &b, ConvolutionMode::Valid);
+        assert_eq!(valid.len(), 71);
+        assert_close(&valid, &full[49..120]);
+    }
+
+    #[test]
+    fn circular_test() {
+        let res = convolve(
+            &[1.0, 2.0, 3.0, 4.0],
+            &[0.0, 1.0],
+            ConvolutionMode::Circular,
+        );
+        assert_eq!(res, vec![4.0, 1.0, 2.0, 3.0]);
+
+        let (a, b) = (signal(90, 5), signal(70, 6));
+        let full = reference(&a, &b);
+        let mut folded = vec![0.0; 90];
+        for (i, v) in full.iter().enumerate() {
+            folded[i % 90] += v;
+        }
+        assert_close(&convolve(&a, &b, ConvolutionMode::Circular), &folded);
+    }
+
+    #[test]
+    fn correlate_test() {
+        let res = correlate(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolutionMode::Full);
+        assert_eq!(res, vec![0.5, 2.0, 3.5, 3.0, 0.0]);
+
+        let circular = correlate(
+            &[1.0, 2.0, 3.0, 4.0],
+            &[1.0, 0.0, 0.0, 1.0],
+            ConvolutionMode::Circular,
+        );
+        // c[k] = a[k] + a[k + 3]
+        assert_eq!(circular, vec![5.0, 3.0, 5.0, 7.0]);
+
+        let (a, b) = (signal(80, 7), signal(60, 8));
+        let reversed: Vec<f64> = b.iter().rev().copied().collect();
+        assert_close(
+            &correlate(&a, &b, ConvolutionMode::Full),
+            &reference(&a, &reversed),
+        );
+        let lags = correlate(&a, &b, ConvolutionMode::Circular);
+        let direct: Vec<f64> = (0..80)
+            .map(|k| (0..60).map(|n| a[(n + k) % 80] * b[n]).sum())
+            .collect();
+        assert_close(&lags, &direct);
+    }
+
+    #[test]
+    fn complex_convolve_test() {
+        for (la, lb) in [(5, 3), (48, 40)] {
+            let (a, b) = (complex_signal(la, 1), complex_signal(lb, 2));
+            let mut expected = vec![Complex { re: 0.0, im: 0.0 }; la + lb - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[i + j] = expected[i + j].add(&x.multiply(y));
+                }
+            }
+            assert_close_complex(&convolve_complex(&a, &b, ConvolutionMode::Full), &expected);
+        }
+    }
+
+    #[test]
+    fn complex_correlate_test() {
+        for length in [6, 50] {
+            let (a, b) = (complex_signal(length, 3), complex_signal(length, 9));
+            let expected: Vec<Complex<f64>> = (0..length)
+                .map(|k| {
+                    (0..length).fold(Complex { re: 0.0, im: 0.0 }, |acc, n| {
+                        acc.add(&a[(n + k) % length].multiply(&b[n].conj()))
+                    })
+                })
+                .collect();
+            assert_close_complex(
+                &correlate_complex(&a, &b, ConvolutionMode::Circular),
+                &expected,
+            );
+            let full = correlate_complex(&a, &b, ConvolutionMode::Full);
+            assert_close_complex(&full[length - 1..length], &expected[..1]);
+        }
+    }
+
+    #[test]
+    fn empty_input_test() {
+        assert!(convolve::<f64>(&[], &[1.0], ConvolutionMode::Full).is_empty());
+        assert!(correlate_complex::<f64>(&[], &[], ConvolutionMode::Circular).is_empty());
+    }
+}
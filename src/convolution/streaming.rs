@@ -0,0 +1,276 @@
+use super::ConvolutionError;
+use crate::complex::Complex;
+use crate::fft::{cached_plan, zero, Direction, FftNum, FftPlan};
+use std::sync::Arc;
+
+/// How consecutive blocks of a stream are stitched together.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BlockMethod {
+    /// Each block is zero-padded and the overlapping tails of the results are summed.
+    #[default]
+    OverlapAdd,
+    /// Each block is prefixed with the end of the previous input and the wrapped-around
+    /// part of the result is discarded.
+    OverlapSave,
+}
+
+/// Convolves an unbounded real signal with a fixed kernel, one chunk at a time.
+///
+/// Input is collected into blocks of [`StreamingConvolver::block_len`] samples; every
+/// completed block yields as many output samples. Concatenating all outputs of
+/// [`StreamingConvolver::process`] and [`StreamingConvolver::flush`] gives the full linear
+/// convolution of the whole stream with the kernel.
+#[derive(Debug, Clone)]
+pub struct StreamingConvolver<T> {
+    method: BlockMethod,
+    kernel_len: usize,
+    block_len: usize,
+    /// Transformed kernel, already divided by the FFT length.
+    kernel: Vec<Complex<T>>,
+    forward: Arc<FftPlan<T>>,
+    inverse: Arc<FftPlan<T>>,
+    pending: Vec<T>,
+    /// Overlap-add: the part of the last block's result reaching into the next blocks.
+    /// Overlap-save: the last `kernel_len - 1` input samples.
+    overlap: Vec<T>,
+    buffer: Vec<Complex<T>>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T: FftNum> StreamingConvolver<T> {
+    /// Uses an FFT length of four times the kernel length, rounded up to a power of two.
+    pub fn new(kernel: &[T], method: BlockMethod) -> Result<Self, ConvolutionError> {
+        Self::with_fft_len(kernel, (4 * kernel.len()).next_power_of_two(), method)
+    }
+
+    /// Every block spans `fft_len - kernel.len() + 1` input samples, so `fft_len` must be
+    /// at least the kernel length.
+    pub fn with_fft_len(
+        kernel: &[T],
+        fft_len: usize,
+        method: BlockMethod,
+    ) -> Result<Self, ConvolutionError> {
+        if kernel.is_empty() {
+            return Err(ConvolutionError::EmptyKernel);
+        }
+        if fft_len < kernel.len() {
+            return Err(ConvolutionError::FftTooShort);
+        }
+
+        let forward = cached_plan::<T>(fft_len, Direction::Forward);
+        let inverse = cached_plan::<T>(fft_len, Direction::Inverse);
+        let mut scratch = vec![zero(); forward.scratch_len().max(inverse.scratch_len())];
+
+        let scale = T::from_usize(fft_len).unwrap();
+        let mut spectrum: Vec<Complex<T>> = (0..fft_len)
+            .map(|i| match kernel.get(i) {
+                Some(&re) => Complex {
+                    re: re / scale,
+                    im: T::zero(),
+                },
+                None => zero(),
+            })
+            .collect();
+        forward.process_with_scratch(&mut spectrum, &mut scratch);
+
+        Ok(StreamingConvolver {
+            method,
+            kernel_len: kernel.len(),
+            block_len: fft_len - kernel.len() + 1,
+            kernel: spectrum,
+            forward,
+            inverse,
+            pending: Vec::new(),
+            overlap: vec![T::zero(); kernel.len() - 1],
+            buffer: vec![zero(); fft_len],
+            scratch,
+        })
+    }
+
+    pub fn block_len(&self) -> usize {
+        self.block_len
+    }
+
+    pub fn method(&self) -> BlockMethod {
+        self.method
+    }
+
+    /// Feeds `input` and returns the output of every block it completes.
+    pub fn process(&mut self, input: &[T]) -> Vec<T> {
+        self.pending.extend_from_slice(input);
+
+        let blocks = self.pending.len() / self.block_len;
+        let mut out = Vec::with_capacity(blocks * self.block_len);
+        for b in 0..blocks {
+            self.process_block(b * self.block_len, &mut out);
+        }
+        self.pending.drain(..blocks * self.block_len);
+        out
+    }
+
+    /// Ends the stream: returns the output still owed for buffered input plus the
+    /// `kernel_len - 1` samples of decay, and resets the convolver for a new stream.
+    pub fn flush(&mut self) -> Vec<T> {
+        let remaining = self.pending.len() + self.kernel_len - 1;
+        let padded = remaining.div_ceil(self.block_len) * self.block_len;
+        self.pending.resize(padded, T::zero());
+
+        let mut out = self.process(&[]);
+        out.truncate(remaining);
+        self.reset();
+        out
+    }
+
+    /// Drops buffered input and overlap, keeping the kernel and plans.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.overlap.iter_mut().for_each(|v| *v = T::zero());
+    }
+
+    fn process_block(&mut self, start: usize, out: &mut Vec<T>) {
+        let overlap_len = self.kernel_len - 1;
+        let block = &self.pending[start..start + self.block_len];
+
+        match self.method {
+            BlockMethod::OverlapAdd => {
+                for (i, slot) in self.buffer.iter_mut().enumerate() {
+                    *slot = match block.get(i) {
+                        Some(&re) => Complex { re, im: T::zero() },
+                        None => zero(),
+                    };
+                }
+            }
+            BlockMethod::OverlapSave => {
+                for (i, slot) in self.buffer.iter_mut().enumerate() {
+                    let re = match i < overlap_len {
+                        true => self.overlap[i],
+                        false => block[i - overlap_len],
+                    };
+                    *slot = Complex { re, im: T::zero() };
+                }
+                let history = self.buffer.len() - overlap_len;
+                for (i, v) in self.overlap.iter_mut().enumerate() {
+                    *v = self.buffer[history + i].re;
+                }
+            }
+        }
+
+        self.forward
+            .process_with_scratch(&mut self.buffer, &mut self.scratch);
+        for (x, h) in self.buffer.iter_mut().zip(self.kernel.iter()) {
+            *x = x.multiply(h);
+        }
+        self.inverse
+            .process_with_scratch(&mut self.buffer, &mut self.scratch);
+
+        match self.method {
+            BlockMethod::OverlapAdd => {
+                for i in 0..self.block_len {
+                    let carried = self.overlap.get(i).copied().unwrap_or(T::zero());
+                    out.push(self.buffer[i].re + carried);
+                }
+                for i in 0..overlap_len {
+                    let carried = self
+                        .overlap
+                        .get(self.block_len + i)
+                        .copied()
+                        .unwrap_or(T::zero());
+                    self.overlap[i] = self.buffer[self.block_len + i].re + carried;
+                }
+            }
+            BlockMethod::OverlapSave => {
+                out.extend(self.buffer[overlap_len..].iter().map(|c| c.re));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convolution::{convolve, ConvolutionMode};
+
+    fn signal(length: usize, seed: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| ((i * 7 + seed) % 11) as f64 - 5.0 + 0.5 * ((i + seed) % 3) as f64)
+            .collect()
+    }
+
+    fn stream(convolver: &mut StreamingConvolver<f64>, x: &[f64], chunks: &[usize]) -> Vec<f64> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        for &size in chunks.iter().cycle() {
+            if start >= x.len() {
+                break;
+            }
+            let end = (start + size).min(x.len());
+            out.extend(convolver.process(&x[start..end]));
+            start = end;
+        }
+        out.extend(convolver.flush());
+        out
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-9));
+    }
+
+    #[test]
+    fn streaming_matches_convolve_test() {
+        let x = signal(1000, 1);
+        for kernel_len in [1, 5, 31, 64] {
+            let kernel = signal(kernel_len, 2);
+            let expected = convolve(&x, &kernel, ConvolutionMode::Full);
+            for method in [BlockMethod::OverlapAdd, BlockMethod::OverlapSave] {
+                let mut convolver = StreamingConvolver::new(&kernel, method).unwrap();
+                assert_close(&stream(&mut convolver, &x, &[1, 17, 64, 3, 250]), &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn short_blocks_test() {
+        // A block shorter than the overlap spreads each result over several later blocks.
+        let x = signal(300, 3);
+        let kernel = signal(20, 4);
+        let expected = convolve(&x, &kernel, ConvolutionMode::Full);
+        for method in [BlockMethod::OverlapAdd, BlockMethod::OverlapSave] {
+            let mut convolver = StreamingConvolver::with_fft_len(&kernel, 24, method).unwrap();
+            assert_eq!(convolver.block_len(), 5);
+            assert_close(&stream(&mut convolver, &x, &[7]), &expected);
+        }
+    }
+
+    #[test]
+    fn output_follows_completed_blocks_test() {
+        let kernel = [1.0, 0.5];
+        let mut convolver =
+            StreamingConvolver::with_fft_len(&kernel, 8, BlockMethod::OverlapSave).unwrap();
+        assert!(convolver.process(&[1.0; 6]).is_empty());
+        assert_eq!(convolver.process(&[1.0; 8]).len(), 14);
+        assert_eq!(convolver.flush().len(), 1);
+    }
+
+    #[test]
+    fn reuse_after_flush_test() {
+        let kernel = signal(9, 5);
+        let x = signal(100, 6);
+        let expected = convolve(&x, &kernel, ConvolutionMode::Full);
+        let mut convolver = StreamingConvolver::new(&kernel, BlockMethod::OverlapAdd).unwrap();
+        assert_close(&stream(&mut convolver, &x, &[13]), &expected);
+        assert_close(&stream(&mut convolver, &x, &[40]), &expected);
+    }
+
+    #[test]
+    fn invalid_kernel_test() {
+        assert_eq!(
+            StreamingConvolver::<f64>::new(&[], BlockMethod::OverlapAdd).err(),
+            Some(ConvolutionError::EmptyKernel)
+        );
+        assert_eq!(
+            StreamingConvolver::with_fft_len(&[1.0; 10], 8, BlockMethod::OverlapSave).err(),
+            Some(ConvolutionError::FftTooShort)
+        );
+    }
+}
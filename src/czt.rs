@@ -0,0 +1,251 @@
+use crate::complex::Complex;
+use crate::fft::{fft_in_place, ifft_in_place, zero, FftNum};
+use std::f64::consts::TAU;
+
+/// Chirp Z-transform: the Z-transform of `x` at the `m` points `z_k = a·w^(-k)`, i.e.
+/// `X_k = Σ x[n]·a^(-n)·w^(nk)`.
+///
+/// `a` is the starting point and `w` the ratio between consecutive points, so the contour
+/// is a spiral that degenerates to an arc of the unit circle when `|a| = |w| = 1`. The sum is
+/// evaluated as a convolution with Bluestein's identity `nk = (n² + k² - (k - n)²)/2` using
+/// three FFTs. Powers of `w` are formed in `f64`, so `|w|` far from one can overflow for long
+/// inputs.
+pub fn czt<T: FftNum>(
+    x: &[Complex<T>],
+    m: usize,
+    w: &Complex<T>,
+    a: &Complex<T>,
+) -> Vec<Complex<T>> {
+    let n = x.len();
+    if n == 0 || m == 0 {
+        return vec![zero(); m];
+    }
+
+    let w = Polar::from(w);
+    let a = Polar::from(a);
+    let length = (n + m - 1).next_power_of_two();
+
+    let mut y = vec![zero(); length];
+    for (i, (slot, xn)) in y.iter_mut().zip(x.iter()).enumerate() {
+        let t = i as f64;
+        *slot = xn.multiply(&a.pow(-t).times(&w.pow(t * t / 2.0)).to_complex());
+    }
+
+    let mut v = vec![zero(); length];
+    for (k, slot) in v.iter_mut().take(m).enumerate() {
+        *slot = w.pow(-((k * k) as f64) / 2.0).to_complex();
+    }
+    for i in 1..n {
+        v[length - i] = w.pow(-((i * i) as f64) / 2.0).to_complex();
+    }
+
+    fft_in_place(&mut y);
+    fft_in_place(&mut v);
+    for (a, b) in y.iter_mut().zip(v.iter()) {
+        *a = a.multiply(b);
+    }
+    ifft_in_place(&mut y);
+
+    (0..m)
+        .map(|k| y[k].multiply(&w.pow((k * k) as f64 / 2.0).to_complex()))
+        .collect()
+}
+
+/// `m` equally spaced DFT values between the normalized frequencies `f_start` and `f_end`
+/// (in cycles per sample, so `1.0` is the sample rate), the end point excluded. With
+/// `f_start = 0`, `f_end = 1` and `m = x.len()` this is the ordinary DFT.
+pub fn zoom_fft<T: FftNum>(
+    x: &[Complex<T>],
+    f_start: f64,
+    f_end: f64,
+    m: usize,
+) -> Vec<Complex<T>> {
+    let step = match m {
+        0 => 0.0,
+        _ => (f_end - f_start) / m as f64,
+    };
+    let a = Polar {
+        modulus: 1.0,
+        angle: TAU * f_start,
+    };
+    let w = Polar {
+        modulus: 1.0,
+        angle: -TAU * step,
+    };
+    czt(x, m, &w.to_complex(), &a.to_complex())
+}
+
+/// Contour points are handled in polar form so that fractional powers are well defined.
+#[derive(Debug, Clone, Copy)]
+struct Polar {
+    modulus: f64,
+    angle: f64,
+}
+
+impl Polar {
+    fn from<T: FftNum>(c: &Complex<T>) -> Self {
+        let (re, im) = (c.re.to_f64().unwrap(), c.im.to_f64().unwrap());
+        Polar {
+            modulus: re.hypot(im),
+            angle: im.atan2(re),
+        }
+    }
+
+    /// Chirp exponents grow like `n²/2`, so the angle is reduced to `[-π, π]` here rather than
+    /// left for `sin_cos`: the rounding error of the product is kept through a fused
+    /// multiply-add, and the part of 2π that `TAU` drops is added back per turn.
+    fn pow(&self, exponent: f64) -> Self {
+        const TAU_LOW: f64 = 2.449_293_598_294_706_4e-16;
+        let angle = self.angle * exponent;
+        let error = self.angle.mul_add(exponent, -angle);
+        let turns = (angle / TAU).round();
+        Polar {
+            modulus: self.modulus.powf(exponent),
+            angle: turns.mul_add(-TAU, angle) - turns * TAU_LOW + error,
+        }
+    }
+
+    fn times(&self, other: &Polar) -> Self {
+        Polar {
+            modulus: self.modulus * other.modulus,
+            angle: self.angle + other.angle,
+        }
+    }
+
+    fn to_complex<T: FftNum>(self) -> Complex<T> {
+        let (sin, cos) = self.angle.sin_cos();
+        Complex {
+            re: T::from_f64(self.modulus * cos).unwrap(),
+            im: T::from_f64(self.modulus * sin).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+
+    fn signal(length: usize) -> Vec<Complex<f64>> {
+        (0..length)
+            .map(|i| Complex {
+                re: ((i * 7 + 3) % 11) as f64 - 5.0,
+                im: ((i * 5 + 1) % 13) as f64 - 6.0,
+            })
+            .collect()
+    }
+
+    /// `Σ x[n]·z_k^(-n)` evaluated term by term.
+    fn direct(x: &[Complex<f64>], m: usize, w: Polar, a: Polar) -> Vec<Complex<f64>> {
+        (0..m)
+            .map(|k| {
+                let z = a.times(&w.pow(-(k as f64)));
+                x.iter()
+                    .enumerate()
+                    .fold(Complex { re: 0.0, im: 0.0 }, |acc, (n, xn)| {
+                        acc.add(&xn.multiply(&z.pow(-(n as f64)).to_complex()))
+                    })
+            })
+            .collect()
+    }
+
+    fn assert_close(a: &[Complex<f64>], b: &[Complex<f64>], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!(
+                (x.re - y.re).abs() < tol && (x.im - y.im).abs() < tol,
+                "{:?} != {:?}",
+                x,
+                y
+            );
+        }
+    }
+
+    #[test]
+    fn zoom_full_band_is_dft_test() {
+        for length in [8, 13, 30] {
+            let x = signal(length);
+            assert_close(&zoom_fft(&x, 0.0, 1.0, length), &fft(x.clone()), 1e-9);
+        }
+    }
+
+    #[test]
+    fn unit_circle_arc_test() {
+        let x = signal(20);
+        let a = Polar {
+            modulus: 1.0,
+            angle: 0.3,
+        };
+        let w = Polar {
+            modulus: 1.0,
+            angle: -0.01,
+        };
+        let res = czt(&x, 50, &w.to_complex(), &a.to_complex());
+        assert_close(&res, &direct(&x, 50, w, a), 1e-8);
+    }
+
+    #[test]
+    fn spiral_contour_test() {
+        let x = signal(16);
+        let a = Polar {
+            modulus: 0.9,
+            angle: 0.2,
+        };
+        let w = Polar {
+            modulus: 1.01,
+            angle: -0.05,
+        };
+        let res = czt(&x, 24, &w.to_complex(), &a.to_complex());
+        assert_close(&res, &direct(&x, 24, w, a), 1e-8);
+    }
+
+    #[test]
+    fn zoom_resolves_close_tones_test() {
+        // Two tones 1.6 bins apart. The 0.100 tone falls between bins 6 and 7 of the 64-point
+        // FFT and shows there at reduced height; the zoomed view samples the band every
+        // 0.0005 cycles/sample, so both peaks appear at full height with a dip between them.
+        let x: Vec<Complex<f64>> = (0..64)
+            .map(|n| {
+                let t = n as f64;
+                let (s1, c1) = (TAU * 0.100 * t).sin_cos();
+                let (s2, c2) = (TAU * 0.125 * t).sin_cos();
+                Complex {
+                    re: c1 + c2,
+                    im: s1 + s2,
+                }
+            })
+            .collect();
+        let zoom = zoom_fft(&x, 0.05, 0.175, 250);
+        let magnitude: Vec<f64> = zoom.iter().map(|c| c.re.hypot(c.im)).collect();
+        let peak = |f: f64| magnitude[((f - 0.05) / 0.0005).round() as usize];
+        let valley = peak(0.1125);
+        assert!(peak(0.100) > 1.5 * valley && peak(0.125) > 1.5 * valley);
+        let grid = fft(x.clone());
+        assert!(grid[6].re.hypot(grid[6].im) < 0.8 * peak(0.100));
+    }
+
+    #[test]
+    fn empty_test() {
+        let one = Complex { re: 1.0, im: 0.0 };
+        assert!(czt::<f64>(&[], 0, &one, &one).is_empty());
+        assert_eq!(
+            czt::<f64>(&[], 2, &one, &one),
+            vec![Complex { re: 0.0, im: 0.0 }; 2]
+        );
+    }
+
+    #[test]
+    fn chirp_angle_reduction_test() {
+        // Exponent 99999²/2 turns the angle about five million times; the reference is the
+        // exact product reduced with 50-digit arithmetic.
+        let w = Polar {
+            modulus: 1.0,
+            angle: -TAU / 1000.0,
+        };
+        let z = w.pow(99_999.0 * 99_999.0 / 2.0);
+        assert!((z.angle + 0.0031415933371251466).abs() < 1e-15);
+        let c: Complex<f64> = z.to_complex();
+        assert!((c.re - 0.9999950651997108).abs() < 1e-15);
+        assert!((c.im + 0.0031415881694115437).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,465 @@
+use crate::complex::Complex;
+use crate::fft::{fft_in_place, zero, FftNum, Normalization};
+use std::f64::consts::PI;
+
+/// The four standard kinds of discrete cosine and sine transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformType {
+    I,
+    II,
+    III,
+    IV,
+}
+
+/// Discrete cosine transform of the given type.
+///
+/// Without normalization the sums follow the usual convention, e.g. DCT-II is
+/// `y[k] = 2 Σ x[n] cos(πk(2n + 1) / 2N)`. [`Normalization::Ortho`] scales the transform
+/// so its matrix is orthogonal, [`Normalization::None`] and [`Normalization::Backward`]
+/// leave it unscaled. DCT-I needs at least two samples; a single sample is returned as is.
+pub fn dct<T: FftNum>(x: &[T], kind: TransformType, norm: Normalization) -> Vec<T> {
+    let length = x.len();
+    if length == 0 || (length == 1 && kind == TransformType::I) {
+        return x.to_vec();
+    }
+
+    let ortho = norm == Normalization::Ortho;
+    let mut input = x.to_vec();
+    if ortho {
+        pre_scale(&mut input, kind, Family::Cosine);
+    }
+
+    let mut y = match kind {
+        TransformType::I => dct1(&input),
+        TransformType::II => dct2(&input),
+        TransformType::III => dct3(&input),
+        TransformType::IV => dct4(&input),
+    };
+
+    if ortho {
+        post_scale(&mut y, kind, Family::Cosine);
+    }
+    y
+}
+
+/// Inverse of [`dct`] with the same `kind`. With [`Normalization::Backward`]
+/// `idct(dct(x)) == x`, with [`Normalization::Ortho`] both directions are orthonormal and
+/// [`Normalization::None`] applies the unscaled transform of the inverse type
+/// (DCT-III for DCT-II and vice versa).
+pub fn idct<T: FftNum>(x: &[T], kind: TransformType, norm: Normalization) -> Vec<T> {
+    let y = dct(x, inverse_type(kind), norm);
+    match norm {
+        Normalization::Backward => unscale(y, kind, Family::Cosine),
+        Normalization::None | Normalization::Ortho => y,
+    }
+}
+
+/// Discrete sine transform of the given type, e.g. DST-II is
+/// `y[k] = 2 Σ x[n] sin(π(k + 1)(2n + 1) / 2N)`. Normalization behaves as for [`dct`].
+pub fn dst<T: FftNum>(x: &[T], kind: TransformType, norm: Normalization) -> Vec<T> {
+    if x.is_empty() {
+        return Vec::new();
+    }
+
+    let ortho = norm == Normalization::Ortho;
+    let mut input = x.to_vec();
+    if ortho {
+        pre_scale(&mut input, kind, Family::Sine);
+    }
+
+    let mut y = match kind {
+        TransformType::I => dst1(&input),
+        TransformType::II => dst2(&input),
+        TransformType::III => dst3(&input),
+        TransformType::IV => dst4(&input),
+    };
+
+    if ortho {
+        post_scale(&mut y, kind, Family::Sine);
+    }
+    y
+}
+
+/// Inverse of [`dst`], see [`idct`] for the normalization modes.
+pub fn idst<T: FftNum>(x: &[T], kind: TransformType, norm: Normalization) -> Vec<T> {
+    let y = dst(x, inverse_type(kind), norm);
+    match norm {
+        Normalization::Backward => unscale(y, kind, Family::Sine),
+        Normalization::None | Normalization::Ortho => y,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Cosine,
+    Sine,
+}
+
+fn inverse_type(kind: TransformType) -> TransformType {
+    match kind {
+        TransformType::II => TransformType::III,
+        TransformType::III => TransformType::II,
+        other => other,
+    }
+}
+
+/// Divides by the factor the unscaled transform pair multiplies a signal by.
+fn unscale<T: FftNum>(y: Vec<T>, kind: TransformType, family: Family) -> Vec<T> {
+    let length = y.len();
+    let factor = match (kind, family) {
+        (TransformType::I, Family::Cosine) if length < 2 => return y,
+        (TransformType::I, Family::Cosine) => 2 * (length - 1),
+        (TransformType::I, Family::Sine) => 2 * (length + 1),
+        _ => 2 * length,
+    };
+    let factor = T::from_usize(factor).unwrap();
+    y.into_iter().map(|v| v / factor).collect()
+}
+
+/// Input weights that make the orthonormal transforms symmetric.
+fn pre_scale<T: FftNum>(x: &mut [T], kind: TransformType, family: Family) {
+    let sqrt2 = T::from_f64(2f64.sqrt()).unwrap();
+    let last = x.len() - 1;
+    match (kind, family) {
+        (TransformType::I, Family::Cosine) => {
+            x[0] = x[0] * sqrt2;
+            x[last] = x[last] * sqrt2;
+        }
+        (TransformType::III, Family::Cosine) => x[0] = x[0] * sqrt2,
+        (TransformType::III, Family::Sine) => x[last] = x[last] * sqrt2,
+        _ => {}
+    }
+}
+
+/// Output weights of the orthonormal transforms.
+fn post_scale<T: FftNum>(y: &mut [T], kind: TransformType, family: Family) {
+    let length = y.len() as f64;
+    let last = y.len() - 1;
+    let scale = |v: &mut T, factor: f64| *v = *v * T::from_f64(factor).unwrap();
+    let common = match (kind, family) {
+        (TransformType::I, Family::Cosine) => 1.0 / (2.0 * (length - 1.0)).sqrt(),
+        (TransformType::I, Family::Sine) => 1.0 / (2.0 * (length + 1.0)).sqrt(),
+        _ => 1.0 / (2.0 * length).sqrt(),
+    };
+    y.iter_mut().for_each(|v| scale(v, common));
+
+    let half = 1.0 / 2f64.sqrt();
+    match (kind, family) {
+        (TransformType::I, Family::Cosine) => {
+            scale(&mut y[0], half);
+            scale(&mut y[last], half);
+        }
+        (TransformType::II, Family::Cosine) => scale(&mut y[0], half),
+        (TransformType::II, Family::Sine) => scale(&mut y[last], half),
+        _ => {}
+    }
+}
+
+// Every type is the real or imaginary part of a zero-padded FFT of about twice the input
+// length, with a phase twist applied before and/or after the transform.
+
+/// `e^(-iπ·numerator/denominator)`.
+fn twist<T: FftNum>(numerator: usize, denominator: usize) -> Complex<T> {
+    let (sin, cos) = (-PI * numerator as f64 / denominator as f64).sin_cos();
+    Complex {
+        re: T::from_f64(cos).unwrap(),
+        im: T::from_f64(sin).unwrap(),
+    }
+}
+
+/// FFT of `a` zero-padded to `length`.
+fn spectrum<T: FftNum>(a: impl Iterator<Item = Complex<T>>, length: usize) -> Vec<Complex<T>> {
+    let mut buffer: Vec<Complex<T>> = a.collect();
+    buffer.resize(length, zero());
+    fft_in_place(&mut buffer);
+    buffer
+}
+
+fn real<T: FftNum>(re: T) -> Complex<T> {
+    Complex { re, im: T::zero() }
+}
+
+fn dct1<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    // Even extension x0 … x[N-1] … x1 of length 2(N-1).
+    let extended = x.iter().chain(x[1..length - 1].iter().rev());
+    let f = spectrum(extended.map(|&v| real(v)), 2 * (length - 1));
+    f[..length].iter().map(|c| c.re).collect()
+}
+
+fn dct2<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    let f = spectrum(x.iter().map(|&v| real(v)), 2 * length);
+    (0..length)
+        .map(|k| two * f[k].multiply(&twist(k, 2 * length)).re)
+        .collect()
+}
+
+fn dct3<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    let twisted = x.iter().enumerate().map(|(n, &v)| {
+        let weight = if n == 0 { v } else { two * v };
+        real(weight).multiply(&twist(n, 2 * length))
+    });
+    let f = spectrum(twisted, 2 * length);
+    f[..length].iter().map(|c| c.re).collect()
+}
+
+fn dct4<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    let f = quarter_shifted(x);
+    (0..length)
+        .map(|k| two * f[k].multiply(&twist(2 * k + 1, 4 * length)).re)
+        .collect()
+}
+
+fn dst1<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    // Odd extension 0, x0 … x[N-1], 0, -x[N-1] … -x0 of length 2(N+1).
+    let extended = std::iter::once(T::zero())
+        .chain(x.iter().copied())
+        .chain(std::iter::once(T::zero()))
+        .chain(x.iter().rev().map(|&v| T::zero() - v));
+    let f = spectrum(extended.map(real), 2 * (length + 1));
+    f[1..=length].iter().map(|c| T::zero() - c.im).collect()
+}
+
+fn dst2<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    let f = spectrum(x.iter().map(|&v| real(v)), 2 * length);
+    (0..length)
+        .map(|k| T::zero() - two * f[k + 1].multiply(&twist(k + 1, 2 * length)).im)
+        .collect()
+}
+
+fn dst3<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    // a[m] = c[m]·x[m-1]·e^(-iπm/2N) for m in 1..=N, with c[N] = 1 and c[m] = 2 otherwise.
+    let twisted = std::iter::once(zero()).chain(x.iter().enumerate().map(|(n, &v)| {
+        let weight = if n == length - 1 { v } else { two * v };
+        real(weight).multiply(&twist(n + 1, 2 * length))
+    }));
+    let f = spectrum(twisted, 2 * length);
+    f[..length].iter().map(|c| T::zero() - c.im).collect()
+}
+
+fn dst4<T: FftNum>(x: &[T]) -> Vec<T> {
+    let length = x.len();
+    let two = T::from_u8(2).unwrap();
+    let f = quarter_shifted(x);
+    (0..length)
+        .map(|k| T::zero() - two * f[k].multiply(&twist(2 * k + 1, 4 * length)).im)
+        .collect()
+}
+
+/// `Σ x[n]·e^(-iπn/2N)·e^(-2πink/2N)`, shared by the type IV transforms.
+fn quarter_shifted<T: FftNum>(x: &[T]) -> Vec<Complex<T>> {
+    let length = x.len();
+    let twisted = x
+        .iter()
+        .enumerate()
+        .map(|(n, &v)| real(v).multiply(&twist(n, 2 * length)));
+    spectrum(twisted, 2 * length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    const TYPES: [TransformType; 4] = [
+        TransformType::I,
+        TransformType::II,
+        TransformType::III,
+        TransformType::IV,
+    ];
+
+    fn signal(length: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| ((i * 7 + 3) % 11) as f64 - 5.0 + 0.25 * (i % 4) as f64)
+            .collect()
+    }
+
+    /// Direct O(n²) sums of the unnormalized definitions.
+    fn reference(x: &[f64], kind: TransformType, family: Family) -> Vec<f64> {
+        let n = x.len() as f64;
+        (0..x.len())
+            .map(|k| {
+                let k = k as f64;
+                let last = x[x.len() - 1];
+                let sum = |f: &dyn Fn(f64) -> f64, range: std::ops::Range<usize>| -> f64 {
+                    range.map(|i| x[i] * f(i as f64)).sum()
+                };
+                match (kind, family) {
+                    (TransformType::I, Family::Cosine) => {
+                        let sign = (-1f64).powi(k as i32);
+                        x[0] + sign * last
+                            + 2.0 * sum(&|i| (PI * k * i / (n - 1.0)).cos(), 1..x.len() - 1)
+                    }
+                    (TransformType::II, Family::Cosine) => {
+                        2.0 * sum(
+                            &|i| (PI * k * (2.0 * i + 1.0) / (2.0 * n)).cos(),
+                            0..x.len(),
+                        )
+                    }
+                    (TransformType::III, Family::Cosine) => {
+                        x[0] + 2.0
+                            * sum(
+                                &|i| (PI * i * (2.0 * k + 1.0) / (2.0 * n)).cos(),
+                                1..x.len(),
+                            )
+                    }
+                    (TransformType::IV, Family::Cosine) => {
+                        2.0 * sum(
+                            &|i| (PI * (2.0 * i + 1.0) * (2.0 * k + 1.0) / (4.0 * n)).cos(),
+                            0..x.len(),
+                        )
+                    }
+                    (TransformType::I, Family::Sine) => {
+                        2.0 * sum(
+                            &|i| (PI * (k + 1.0) * (i + 1.0) / (n + 1.0)).sin(),
+                            0..x.len(),
+                        )
+                    }
+                    (TransformType::II, Family::Sine) => {
+                        2.0 * sum(
+                            &|i| (PI * (k + 1.0) * (2.0 * i + 1.0) / (2.0 * n)).sin(),
+                            0..x.len(),
+                        )
+                    }
+                    (TransformType::III, Family::Sine) => {
+                        let sign = (-1f64).powi(k as i32);
+                        sign * last
+                            + 2.0
+                                * sum(
+                                    &|i| (PI * (2.0 * k + 1.0) * (i + 1.0) / (2.0 * n)).sin(),
+                                    0..x.len() - 1,
+                                )
+                    }
+                    (TransformType::IV, Family::Sine) => {
+                        2.0 * sum(
+                            &|i| (PI * (2.0 * k + 1.0) * (2.0 * i + 1.0) / (4.0 * n)).sin(),
+                            0..x.len(),
+                        )
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        assert!(
+            a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-9),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn dct_matches_reference_test() {
+        for length in [2, 3, 4, 7, 8, 16, 31] {
+            let x = signal(length);
+            for kind in TYPES {
+                assert_close(
+                    &dct(&x, kind, Normalization::None),
+                    &reference(&x, kind, Family::Cosine),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dst_matches_reference_test() {
+        for length in [1, 2, 3, 4, 7, 8, 16, 31] {
+            let x = signal(length);
+            for kind in TYPES {
+                assert_close(
+                    &dst(&x, kind, Normalization::None),
+                    &reference(&x, kind, Family::Sine),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn backward_round_trip_test() {
+        for length in [2, 5, 12, 33] {
+            let x = signal(length);
+            for kind in TYPES {
+                assert_close(
+                    &idct(
+                        &dct(&x, kind, Normalization::Backward),
+                        kind,
+                        Normalization::Backward,
+                    ),
+                    &x,
+                );
+                assert_close(
+                    &idst(
+                        &dst(&x, kind, Normalization::Backward),
+                        kind,
+                        Normalization::Backward,
+                    ),
+                    &x,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ortho_test() {
+        for length in [2, 6, 17] {
+            let x = signal(length);
+            let energy = |v: &[f64]| v.iter().map(|a| a * a).sum::<f64>();
+            for kind in TYPES {
+                let y = dct(&x, kind, Normalization::Ortho);
+                assert!((energy(&y) - energy(&x)).abs() < 1e-9);
+                assert_close(&idct(&y, kind, Normalization::Ortho), &x);
+
+                let y = dst(&x, kind, Normalization::Ortho);
+                assert!((energy(&y) - energy(&x)).abs() < 1e-9);
+                assert_close(&idst(&y, kind, Normalization::Ortho), &x);
+            }
+        }
+    }
+
+    #[test]
+    fn ortho_dct2_values_test() {
+        // Orthonormal DCT-II of a constant puts all the energy in the first coefficient.
+        let y = dct(&[1.0; 4], TransformType::II, Normalization::Ortho);
+        assert_close(&y, &[2.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn decimal_test() {
+        let x = [dec!(1.0), dec!(2.0), dec!(3.0), dec!(4.0)];
+        let y = dct(&x, TransformType::II, Normalization::None);
+        assert!((y[0] - dec!(20.0)).abs() < dec!(1e-12));
+        let back: Vec<Decimal> = idct(&y, TransformType::II, Normalization::Backward);
+        assert!(back
+            .iter()
+            .zip(x.iter())
+            .all(|(a, b)| (*a - *b).abs() < dec!(1e-12)));
+    }
+
+    #[test]
+    fn degenerate_lengths_test() {
+        assert!(dct::<f64>(&[], TransformType::II, Normalization::None).is_empty());
+        assert_eq!(
+            dct(&[3.0], TransformType::I, Normalization::Ortho),
+            vec![3.0]
+        );
+        assert_eq!(
+            dct(&[3.0], TransformType::II, Normalization::None),
+            vec![6.0]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::radix2::Radix2;
+use super::{zero, Direction, FftNum};
+use crate::complex::Complex;
+use std::f64::consts::PI;
+
+/// Bluestein's chirp-z algorithm: rewrites a transform of any length as a circular
+/// convolution whose length is a power of two, which is then evaluated with radix-2 FFTs.
+#[derive(Debug, Clone)]
+pub(super) struct Bluestein<T> {
+    chirp: Vec<Complex<T>>,
+    /// Transformed convolution kernel, already divided by the padded length.
+    kernel: Vec<Complex<T>>,
+    inner: Radix2<T>,
+}
+
+impl<T: FftNum> Bluestein<T> {
+    pub(super) fn new(length: usize, direction: Direction) -> Self {
+        let padded = padded_len(length);
+        let inner = Radix2::new(padded, Direction::Forward);
+
+        // w[k] = e^(∓πi k² / n); k² is reduced mod 2n first to keep the angle small.
+        let chirp: Vec<Complex<T>> = (0..length)
+            .map(|k| {
+                let angle = direction.sign() * PI * ((k * k) % (2 * length)) as f64 / length as f64;
+                let (sin, cos) = angle.sin_cos();
+                Complex {
+                    re: T::from_f64(cos).unwrap(),
+                    im: T::from_f64(sin).unwrap(),
+                }
+            })
+            .collect();
+
+        let mut kernel = vec![zero(); padded];
+        for (k, w) in chirp.iter().enumerate() {
+            kernel[k] = w.conj();
+            if k > 0 {
+                kernel[padded - k] = w.conj();
+            }
+        }
+        inner.process(&mut kernel);
+
+        let scale = T::from_usize(padded).unwrap();
+        kernel.iter_mut().for_each(|c| {
+            c.re = c.re / scale;
+            c.im = c.im / scale;
+        });
+
+        Bluestein {
+            chirp,
+            kernel,
+            inner,
+        }
+    }
+
+    /// `scratch` must hold at least [`padded_len`] elements.
+    pub(super) fn process(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let work = &mut scratch[..self.kernel.len()];
+        for (k, slot) in work.iter_mut().enumerate() {
+            *slot = self.load(x, k);
+        }
+        self.inner.process(work);
+
+        for (a, b) in work.iter_mut().zip(self.kernel.iter()) {
+            *a = convolve(a, b);
+        }
+        self.inner.process(work);
+
+        for (k, xk) in x.iter_mut().enumerate() {
+            *xk = work[k].conj().multiply(&self.chirp[k]);
+        }
+    }
+
+    /// [`Bluestein::process`] with the pointwise steps and the inner transforms spread over
+    /// the rayon pool, bit-identical to the serial path.
+    #[cfg(feature = "rayon")]
+    pub(super) fn process_parallel(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        use super::parallel::MIN_CHUNK;
+        use rayon::prelude::*;
+
+        let work = &mut scratch[..self.kernel.len()];
+        work.par_iter_mut()
+            .with_min_len(MIN_CHUNK)
+            .enumerate()
+            .for_each(|(k, slot)| *slot = self.load(x, k));
+        self.inner.process_parallel(work);
+
+        work.par_iter_mut()
+            .with_min_len(MIN_CHUNK)
+            .zip(self.kernel.par_iter())
+            .for_each(|(a, b)| *a = convolve(a, b));
+        self.inner.process_parallel(work);
+
+        x.par_iter_mut()
+            .with_min_len(MIN_CHUNK)
+            .enumerate()
+            .for_each(|(k, xk)| *xk = work[k].conj().multiply(&self.chirp[k]));
+    }
+
+    #[inline]
+    fn load(&self, x: &[Complex<T>], k: usize) -> Complex<T> {
+        match k < x.len() {
+            true => x[k].multiply(&self.chirp[k]),
+            false => zero(),
+        }
+    }
+}
+
+/// Multiplies by the transformed kernel and conjugates, so that the second forward
+/// transform yields the inverse through `conj(fft(conj(.)))`.
+#[inline]
+fn convolve<T: FftNum>(a: &Complex<T>, kernel: &Complex<T>) -> Complex<T> {
+    a.multiply(kernel).conj()
+}
+
+/// Length of the power-of-two convolution a transform of `length` is embedded in.
+pub(super) fn padded_len(length: usize) -> usize {
+    (2 * length - 1).next_power_of_two()
+}
@@ -0,0 +1,243 @@
+use super::{zero, Direction};
+use crate::complex::Complex;
+use rust_decimal::Decimal;
+
+/// Forward transform of `Decimal` input with twiddle factors computed in `Decimal`.
+///
+/// The generic [`fft`](super::fft) derives its twiddles from `f64`, which caps `Decimal`
+/// results at roughly 16 significant digits. Here every root of unity is summed from its
+/// Taylor series in `Decimal` and is within `1e-27` of the true value. The transform is a
+/// recursive Cooley-Tukey over the prime factors of the length that looks every twiddle up
+/// in that table, so no `f64` value enters the computation.
+///
+/// Each output is a sum of products rounded to `Decimal`'s 28 digits, so the error grows with
+/// the length and the magnitude of the input. The tests check a length-10 transform of
+/// integers up to 10 against a 40-digit reference to within `1e-24`, and round trips of
+/// lengths up to 97 to within `1e-25`. Prime lengths fall back to an `O(n²)` evaluation.
+///
+/// # Panics
+///
+/// If an intermediate value exceeds the `Decimal` range of about `7.9e28`.
+pub fn fft_decimal(x: Vec<Complex<Decimal>>) -> Vec<Complex<Decimal>> {
+    transform(x, Direction::Forward)
+}
+
+/// Inverse of [`fft_decimal`], scaled by `1/n`.
+pub fn ifft_decimal(x: Vec<Complex<Decimal>>) -> Vec<Complex<Decimal>> {
+    let length = Decimal::from(x.len().max(1));
+    transform(x, Direction::Inverse)
+        .into_iter()
+        .map(|c| Complex {
+            re: c.re / length,
+            im: c.im / length,
+        })
+        .collect()
+}
+
+fn transform(x: Vec<Complex<Decimal>>, direction: Direction) -> Vec<Complex<Decimal>> {
+    let length = x.len();
+    if length <= 1 {
+        return x;
+    }
+
+    let table: Vec<Complex<Decimal>> = (0..length)
+        .map(|k| match direction {
+            Direction::Forward => root_of_unity(k, length).conj(),
+            Direction::Inverse => root_of_unity(k, length),
+        })
+        .collect();
+    cooley_tukey(&x, &table)
+}
+
+/// DFT of `x` with `table[j]` holding `ω^j` for the root `ω` of the whole transform, of
+/// which `x.len()` must be a divisor.
+fn cooley_tukey(x: &[Complex<Decimal>], table: &[Complex<Decimal>]) -> Vec<Complex<Decimal>> {
+    let length = x.len();
+    if length == 1 {
+        return x.to_vec();
+    }
+
+    let radix = smallest_factor(length);
+    let sub_len = length / radix;
+    let stride = table.len() / length;
+    let subs: Vec<Vec<Complex<Decimal>>> = (0..radix)
+        .map(|j| {
+            let sub: Vec<Complex<Decimal>> = x.iter().skip(j).step_by(radix).cloned().collect();
+            cooley_tukey(&sub, table)
+        })
+        .collect();
+
+    (0..length)
+        .map(|k| {
+            subs.iter().enumerate().fold(zero(), |acc, (j, sub)| {
+                let twiddle = &table[(stride * j * k) % table.len()];
+                acc.add(&sub[k % sub_len].multiply(twiddle))
+            })
+        })
+        .collect()
+}
+
+fn smallest_factor(n: usize) -> usize {
+    (2..)
+        .take_while(|p| p * p <= n)
+        .find(|p| n.is_multiple_of(*p))
+        .unwrap_or(n)
+}
+
+/// `e^(2πik/n)`, reduced to an angle of at most `π/4` before the series are summed.
+fn root_of_unity(k: usize, n: usize) -> Complex<Decimal> {
+    // 2πk/n = (π/2)·(quadrant + rest/n)
+    let quarter = 4 * (k % n);
+    let (quadrant, rest) = (quarter / n, quarter % n);
+    let (cos, sin) = match 2 * rest <= n {
+        true => sin_cos(rest, n),
+        false => {
+            let (c, s) = sin_cos(n - rest, n);
+            (s, c)
+        }
+    };
+    match quadrant {
+        0 => Complex { re: cos, im: sin },
+        1 => Complex { re: -sin, im: cos },
+        2 => Complex { re: -cos, im: -sin },
+        _ => Complex { re: sin, im: -cos },
+    }
+}
+
+/// Cosine and sine of `(π/2)·r/n` for `r/n ≤ 1/2`, from their Taylor series.
+fn sin_cos(r: usize, n: usize) -> (Decimal, Decimal) {
+    let angle = Decimal::HALF_PI * Decimal::from(r) / Decimal::from(n);
+    let square = angle * angle;
+    let (mut cos, mut sin) = (Decimal::ONE, angle);
+    let (mut cos_term, mut sin_term) = (Decimal::ONE, angle);
+    let mut i = 1u32;
+    while !(cos_term.is_zero() && sin_term.is_zero()) {
+        cos_term = -cos_term * square / Decimal::from((2 * i - 1) * (2 * i));
+        sin_term = -sin_term * square / Decimal::from((2 * i) * (2 * i + 1));
+        cos += cos_term;
+        sin += sin_term;
+        i += 1;
+    }
+    (cos, sin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+
+    fn complex(re: &str, im: &str) -> Complex<Decimal> {
+        Complex {
+            re: Decimal::from_str(re).unwrap(),
+            im: Decimal::from_str(im).unwrap(),
+        }
+    }
+
+    fn error(a: &Complex<Decimal>, b: &Complex<Decimal>) -> Decimal {
+        (a.re - b.re).abs().max((a.im - b.im).abs())
+    }
+
+    #[test]
+    fn root_of_unity_test() {
+        let half = dec!(0.7071067811865475244008443621);
+        for (k, expected) in [
+            (0, complex("1", "0")),
+            (1, Complex { re: half, im: half }),
+            (2, complex("0", "1")),
+            (
+                3,
+                Complex {
+                    re: -half,
+                    im: half,
+                },
+            ),
+            (4, complex("-1", "0")),
+            (6, complex("0", "-1")),
+            (
+                7,
+                Complex {
+                    re: half,
+                    im: -half,
+                },
+            ),
+        ] {
+            assert!(
+                error(&root_of_unity(k, 8), &expected) <= dec!(1e-27),
+                "{}",
+                k
+            );
+        }
+
+        // cos(2π/7) and sin(2π/7) to 28 digits.
+        let expected = complex(
+            "0.6234898018587335305250048840",
+            "0.7818314824680298087084445267",
+        );
+        assert!(error(&root_of_unity(1, 7), &expected) <= dec!(1e-27));
+        let expected = complex(
+            "-0.9009688679024191262361023195",
+            "-0.4338837391175581204757683328",
+        );
+        assert!(error(&root_of_unity(4, 7), &expected) <= dec!(1e-27));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn high_precision_test() {
+        // The input of `fft_test`, with the DFT evaluated to 40 digits.
+        let x = vec![
+            complex("-2", "4"), complex("5", "-5"), complex("10", "4"), complex("-1", "-9"),
+            complex("-8", "3"), complex("9", "-5"), complex("-8", "-4"), complex("3", "-7"),
+            complex("-10", "1"), complex("-8", "1"),
+        ];
+        let expected = [
+            complex("-10", "-17"),
+            complex("0.438028706712048366410372877", "-9.79580664177555419921492524"),
+            complex("-9.91416533224852669781834332", "-20.1164788006066669929488937"),
+            complex("-13.0359156741217412221774942", "-10.0214765739064800046556976"),
+            complex("10.5928660205959773663727877", "14.8300461450318505069876061"),
+            complex("-26", "33"),
+            complex("35.3858477431518144459235358", "-15.5382500775312195962151271"),
+            complex("-13.762458202127102108072961", "-4.10313522359162726302686545"),
+            complex("8.9354515685007348855220198", "32.8246827331060360821764147"),
+            complex("-2.6396548304632050361599177", "35.9204184392736614668974883"),
+        ];
+        let result = fft_decimal(x.clone());
+        for (a, b) in result.iter().zip(expected.iter()) {
+            assert!(error(a, b) < dec!(1e-24), "{:?} != {:?}", a, b);
+        }
+
+        // The f64 twiddles of the generic path are far less accurate.
+        let generic = fft(x);
+        assert!(generic.iter().zip(expected.iter()).any(|(a, b)| error(a, b) > dec!(1e-15)));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for length in [1, 2, 8, 12, 30, 64, 97] {
+            let x: Vec<Complex<Decimal>> = (0..length)
+                .map(|i| Complex {
+                    re: Decimal::from(((i * 7 + 3) % 11) as i64 - 5) / dec!(3),
+                    im: Decimal::from(((i * 5 + 1) % 13) as i64 - 6) / dec!(7),
+                })
+                .collect();
+            let y = ifft_decimal(fft_decimal(x.clone()));
+            for (a, b) in x.iter().zip(y.iter()) {
+                assert!(error(a, b) < dec!(1e-25), "{}: {:?} != {:?}", length, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn impulse_test() {
+        // The transform of a shifted impulse is the twiddle table itself.
+        let mut x = vec![complex("0", "0"); 12];
+        x[1] = complex("1", "0");
+        let result = fft_decimal(x);
+        for (k, c) in result.iter().enumerate() {
+            assert_eq!(*c, root_of_unity(k, 12).conj());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use super::FftNum;
+use crate::complex::Complex;
+
+/// Neighbours smaller than this fraction of a peak are treated as empty bins.
+const NEGLIGIBLE: f64 = 1e-9;
+
+/// Frequencies of the bins of an `n`-point [`fft`](super::fft), in the unit of `sample_rate`:
+/// `0, 1, …, ⌈n/2⌉-1, -⌊n/2⌋, …, -1` times `sample_rate / n`.
+pub fn fftfreq(n: usize, sample_rate: f64) -> Vec<f64> {
+    let positive = n.div_ceil(2);
+    (0..n)
+        .map(|k| match k < positive {
+            true => bin_to_frequency(k as f64, n, sample_rate),
+            false => bin_to_frequency(k as f64 - n as f64, n, sample_rate),
+        })
+        .collect()
+}
+
+/// Frequencies of the `n/2 + 1` bins returned by [`rfft`](super::rfft) for `n` samples.
+pub fn rfftfreq(n: usize, sample_rate: f64) -> Vec<f64> {
+    (0..n / 2 + 1)
+        .map(|k| bin_to_frequency(k as f64, n, sample_rate))
+        .collect()
+}
+
+/// Frequency of a (possibly fractional) bin of an `n`-point transform.
+pub fn bin_to_frequency(bin: f64, n: usize, sample_rate: f64) -> f64 {
+    bin * sample_rate / n as f64
+}
+
+/// Fractional bin of `frequency` in an `n`-point transform; negative for negative frequencies.
+pub fn frequency_to_bin(frequency: f64, n: usize, sample_rate: f64) -> f64 {
+    frequency * n as f64 / sample_rate
+}
+
+/// Index of the `n`-point [`fft`](super::fft) bin closest to `frequency`. Negative frequencies
+/// and frequencies beyond the sample rate wrap around, so for `0 ≤ frequency ≤ sample_rate/2`
+/// this is also the index into the [`rfft`](super::rfft) output.
+pub fn nearest_bin(frequency: f64, n: usize, sample_rate: f64) -> usize {
+    assert!(n > 0, "transform length must be positive");
+    let bin = frequency_to_bin(frequency, n, sample_rate).round() as i64;
+    bin.rem_euclid(n as i64) as usize
+}
+
+/// A spectral peak refined by quadratic interpolation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Peak {
+    /// Fractional bin index of the interpolated maximum.
+    pub bin: f64,
+    /// `bin` converted to the unit of the sample rate; negative in the upper half of a full
+    /// spectrum.
+    pub frequency: f64,
+    /// Interpolated magnitude `|X|` at the maximum. A real tone of amplitude `A` analysed with
+    /// window `w` peaks at roughly `A·Σw/2`, so [`Peak::amplitude`] divides that back out.
+    pub magnitude: f64,
+}
+
+impl Peak {
+    /// Amplitude of the real sinusoid behind the peak, given the sum of the analysis window
+    /// (`n` for a rectangular window).
+    pub fn amplitude(&self, window_sum: f64) -> f64 {
+        2.0 * self.magnitude / window_sum
+    }
+}
+
+/// Refines the local maximum at `bin` by fitting a parabola through the log-magnitudes of it
+/// and its two neighbours. `spectrum` is the output of an `n`-point [`fft`](super::fft) or
+/// [`rfft`](super::rfft), `n` being the transform length. Bins at either end of the slice are
+/// returned as is.
+pub fn interpolate_peak<T: FftNum>(
+    spectrum: &[Complex<T>],
+    bin: usize,
+    n: usize,
+    sample_rate: f64,
+) -> Peak {
+    assert!(bin < spectrum.len(), "bin is outside the spectrum");
+    let center = magnitude(&spectrum[bin]);
+    let (offset, peak) = match bin > 0 && bin + 1 < spectrum.len() {
+        true => {
+            let (left, right) = (magnitude(&spectrum[bin - 1]), magnitude(&spectrum[bin + 1]));
+            // A neighbour at the noise floor (a tone exactly on a bin with a rectangular
+            // window) carries no shape information and would skew the fit.
+            let floor = center * NEGLIGIBLE;
+            match left > floor && right > floor {
+                true => quadratic(left.ln(), center.ln(), right.ln()).unwrap_or((0.0, center)),
+                false => (0.0, center),
+            }
+        }
+        false => (0.0, center),
+    };
+
+    let fractional = bin as f64 + offset;
+    let signed = match fractional > n as f64 / 2.0 {
+        true => fractional - n as f64,
+        false => fractional,
+    };
+    Peak {
+        bin: fractional,
+        frequency: bin_to_frequency(signed, n, sample_rate),
+        magnitude: peak,
+    }
+}
+
+/// Interpolated local maxima of `|spectrum|` at least `min_magnitude` high, strongest first.
+/// See [`interpolate_peak`] for the meaning of `n`.
+pub fn find_peaks<T: FftNum>(
+    spectrum: &[Complex<T>],
+    n: usize,
+    sample_rate: f64,
+    min_magnitude: f64,
+) -> Vec<Peak> {
+    let magnitudes: Vec<f64> = spectrum.iter().map(magnitude).collect();
+    let mut peaks: Vec<Peak> = (0..magnitudes.len())
+        .filter(|&k| {
+            let m = magnitudes[k];
+            m >= min_magnitude
+                && (k == 0 || m > magnitudes[k - 1])
+                && (k + 1 == magnitudes.len() || m >= magnitudes[k + 1])
+        })
+        .map(|k| interpolate_peak(spectrum, k, n, sample_rate))
+        .collect();
+    peaks.sort_by(|a, b| b.magnitude.total_cmp(&a.magnitude));
+    peaks
+}
+
+fn magnitude<T: FftNum>(c: &Complex<T>) -> f64 {
+    c.re.to_f64().unwrap().hypot(c.im.to_f64().unwrap())
+}
+
+/// Offset from the middle sample and value at the vertex of the parabola through three
+/// equally spaced log-magnitudes, or `None` if they do not form a maximum.
+fn quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    let curvature = a - 2.0 * b + c;
+    match curvature < 0.0 {
+        true => {
+            let p = 0.5 * (a - c) / curvature;
+            Some((p, (b - 0.25 * (a - c) * p).exp()))
+        }
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::{fft, rfft};
+    use crate::window::{self, Symmetry};
+    use std::f64::consts::TAU;
+
+    fn tone(length: usize, frequency: f64, amplitude: f64, sample_rate: f64) -> Vec<f64> {
+        (0..length)
+            .map(|i| amplitude * (TAU * frequency * i as f64 / sample_rate + 0.4).cos())
+            .collect()
+    }
+
+    #[test]
+    fn fftfreq_test() {
+        assert_eq!(fftfreq(4, 8.0), vec![0.0, 2.0, -4.0, -2.0]);
+        assert_eq!(fftfreq(5, 5.0), vec![0.0, 1.0, 2.0, -2.0, -1.0]);
+        assert!(fftfreq(0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn rfftfreq_test() {
+        assert_eq!(rfftfreq(4, 8.0), vec![0.0, 2.0, 4.0]);
+        assert_eq!(rfftfreq(5, 5.0), vec![0.0, 1.0, 2.0]);
+        assert_eq!(rfftfreq(8, 8.0).len(), rfft(&[0.0f64; 8]).len());
+    }
+
+    #[test]
+    fn bin_conversion_test() {
+        assert_eq!(bin_to_frequency(2.5, 100, 1000.0), 25.0);
+        assert_eq!(frequency_to_bin(25.0, 100, 1000.0), 2.5);
+        assert_eq!(nearest_bin(26.0, 100, 1000.0), 3);
+        assert_eq!(nearest_bin(-10.0, 100, 1000.0), 99);
+        assert_eq!(nearest_bin(1010.0, 100, 1000.0), 1);
+    }
+
+    #[test]
+    fn nearest_bin_matches_fftfreq_test() {
+        let freqs = fftfreq(9, 90.0);
+        for (k, &f) in freqs.iter().enumerate() {
+            assert_eq!(nearest_bin(f, 9, 90.0), k);
+        }
+    }
+
+    #[test]
+    fn interpolated_tone_test() {
+        let (n, fs) = (256, 1000.0);
+        let w: Vec<f64> = window::hann(n, Symmetry::Periodic);
+        let mut x = tone(n, 123.4, 2.5, fs);
+        window::apply(&mut x, &w);
+        let spectrum = rfft(&x);
+        let peaks = find_peaks(&spectrum, n, fs, 1.0);
+        let peak = peaks[0];
+        assert!((peak.frequency - 123.4).abs() < 0.05, "{}", peak.frequency);
+        let amplitude = peak.amplitude(w.iter().sum());
+        // Log-parabolic interpolation of a Hann main lobe is biased by a few percent at most.
+        assert!((amplitude - 2.5).abs() < 0.1, "{}", amplitude);
+    }
+
+    #[test]
+    fn interpolation_beats_nearest_bin_test() {
+        let (n, fs) = (64, 64.0);
+        let w: Vec<f64> = window::hann(n, Symmetry::Periodic);
+        let mut x = tone(n, 10.3, 1.0, fs);
+        window::apply(&mut x, &w);
+        let spectrum = rfft(&x);
+        let bin = nearest_bin(10.3, n, fs);
+        assert_eq!(bin, 10);
+        let peak = interpolate_peak(&spectrum, bin, n, fs);
+        assert!((peak.frequency - 10.3).abs() < (10.0f64 - 10.3).abs() / 5.0);
+    }
+
+    #[test]
+    fn full_spectrum_negative_peak_test() {
+        let (n, fs) = (128, 128.0);
+        let w: Vec<f64> = window::hann(n, Symmetry::Periodic);
+        let mut real = tone(n, 20.25, 1.0, fs);
+        window::apply(&mut real, &w);
+        let x: Vec<Complex<f64>> = real.into_iter().map(|re| Complex { re, im: 0.0 }).collect();
+        let peaks = find_peaks(&fft(x), n, fs, 1.0);
+        assert_eq!(peaks.len(), 2);
+        let mut frequencies: Vec<f64> = peaks.iter().map(|p| p.frequency).collect();
+        frequencies.sort_by(f64::total_cmp);
+        assert!((frequencies[0] + 20.25).abs() < 0.02 && (frequencies[1] - 20.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn peak_order_and_threshold_test() {
+        let (n, fs) = (128, 128.0);
+        let x: Vec<f64> = tone(n, 10.0, 1.0, fs)
+            .iter()
+            .zip(tone(n, 30.0, 3.0, fs))
+            .map(|(a, b)| a + b)
+            .collect();
+        let peaks = find_peaks(&rfft(&x), n, fs, 1.0);
+        assert_eq!(peaks.len(), 2);
+        assert!((peaks[0].frequency - 30.0).abs() < 0.05);
+        assert!((peaks[1].frequency - 10.0).abs() < 0.05);
+        assert!((peaks[0].amplitude(n as f64) - 3.0).abs() < 1e-3);
+        assert_eq!(find_peaks(&rfft(&x), n, fs, 100.0).len(), 1);
+    }
+}
@@ -0,0 +1,221 @@
+use super::{rotate, scale, twiddles, zero, Direction, FftNum};
+use crate::complex::Complex;
+
+/// Splits `length` into the radices used by the Stockham passes: fours first, then a
+/// remaining two, then the odd primes in ascending order.
+pub(super) fn factorize(mut length: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    while length.is_multiple_of(4) {
+        factors.push(4);
+        length /= 4;
+    }
+    if length.is_multiple_of(2) {
+        factors.push(2);
+        length /= 2;
+    }
+
+    let mut p = 3;
+    while p * p <= length {
+        while length.is_multiple_of(p) {
+            factors.push(p);
+            length /= p;
+        }
+        p += 2;
+    }
+    if length > 1 {
+        factors.push(length);
+    }
+
+    factors
+}
+
+/// Self-sorting (Stockham) mixed-radix transform. Every pass reads from one buffer and
+/// writes to the other, so the scratch buffer must be as long as the input.
+#[derive(Debug, Clone)]
+pub(super) struct MixedRadix<T> {
+    factors: Vec<usize>,
+    twiddles: Vec<Complex<T>>,
+}
+
+impl<T: FftNum> MixedRadix<T> {
+    pub(super) fn new(length: usize, factors: Vec<usize>, direction: Direction) -> Self {
+        MixedRadix {
+            factors,
+            twiddles: twiddles(length, length, direction),
+        }
+    }
+
+    pub(super) fn process(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        self.run(x, scratch, pass);
+    }
+
+    /// [`MixedRadix::process`] with every pass spread over the rayon pool, bit-identical
+    /// to the serial passes.
+    #[cfg(feature = "rayon")]
+    pub(super) fn process_parallel(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        self.run(x, scratch, pass_parallel);
+    }
+
+    fn run(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>], pass: Pass<T>) {
+        let scratch = &mut scratch[..x.len()];
+        let mut span = 1;
+        let mut in_x = true;
+        for &radix in &self.factors {
+            match in_x {
+                true => pass(x, scratch, radix, span, &self.twiddles),
+                false => pass(scratch, x, radix, span, &self.twiddles),
+            }
+            in_x = !in_x;
+            span *= radix;
+        }
+
+        if !in_x {
+            x.clone_from_slice(scratch);
+        }
+    }
+}
+
+/// Signature shared by [`pass`] and its parallel counterpart.
+type Pass<T> = fn(&[Complex<T>], &mut [Complex<T>], usize, usize, &[Complex<T>]);
+
+/// One radix-`radix` pass. `span` is the product of the radices already applied, i.e. the
+/// length of the sub-transforms being combined.
+fn pass<T: FftNum>(
+    src: &[Complex<T>],
+    dst: &mut [Complex<T>],
+    radix: usize,
+    span: usize,
+    twiddles: &[Complex<T>],
+) {
+    for j in 0..src.len() / radix {
+        let base = (j / span) * span * radix + j % span;
+        butterfly(src, j, radix, span, twiddles, |q, value| {
+            dst[base + q * span] = value
+        });
+    }
+}
+
+/// [`pass`] spread over the rayon pool. Output block `b` (`span * radix` elements) only
+/// depends on `src`, so blocks are processed independently while there are enough of
+/// them; otherwise every block is cut into runs of columns `k` that own a piece of each of
+/// its `radix` rows.
+#[cfg(feature = "rayon")]
+fn pass_parallel<T: FftNum>(
+    src: &[Complex<T>],
+    dst: &mut [Complex<T>],
+    radix: usize,
+    span: usize,
+    twiddles: &[Complex<T>],
+) {
+    use super::parallel::MIN_CHUNK;
+    use rayon::prelude::*;
+
+    let block_len = span * radix;
+    let threads = rayon::current_num_threads();
+    if dst.len() / block_len >= 2 * threads {
+        dst.par_chunks_mut(block_len)
+            .with_min_len(MIN_CHUNK / block_len + 1)
+            .enumerate()
+            .for_each(|(b, block)| {
+                for k in 0..span {
+                    butterfly(src, b * span + k, radix, span, twiddles, |q, value| {
+                        block[k + q * span] = value
+                    });
+                }
+            });
+        return;
+    }
+
+    let run = (span / (4 * threads)).max(MIN_CHUNK / radix + 1);
+    let mut units: Vec<(usize, Vec<&mut [Complex<T>]>)> = Vec::new();
+    for (b, block) in dst.chunks_mut(block_len).enumerate() {
+        let first = units.len();
+        for row in block.chunks_mut(span) {
+            for (r, piece) in row.chunks_mut(run).enumerate() {
+                match units.get_mut(first + r) {
+                    Some((_, rows)) => rows.push(piece),
+                    None => units.push((b * span + r * run, vec![piece])),
+                }
+            }
+        }
+    }
+    units.into_par_iter().for_each(|(j0, mut rows)| {
+        for i in 0..rows[0].len() {
+            butterfly(src, j0 + i, radix, span, twiddles, |q, value| {
+                rows[q][i] = value
+            });
+        }
+    });
+}
+
+/// Combines the `radix` inputs of group `j` and hands output `q` of the butterfly to
+/// `store`, which the serial and parallel passes map to the same position of `dst`.
+#[inline]
+fn butterfly<T: FftNum>(
+    src: &[Complex<T>],
+    j: usize,
+    radix: usize,
+    span: usize,
+    twiddles: &[Complex<T>],
+    mut store: impl FnMut(usize, Complex<T>),
+) {
+    let length = src.len();
+    let groups = length / radix;
+    let stride = length / (span * radix);
+    let k = j % span;
+    let v = |r: usize| src[j + r * groups].multiply(&twiddles[r * k * stride]);
+    match radix {
+        2 => {
+            let (v0, v1) = (v(0), v(1));
+            store(0, v0.add(&v1));
+            store(1, v0.substract(&v1));
+        }
+        3 => {
+            let w = &twiddles[length / 3];
+            let (v0, v1, v2) = (v(0), v(1), v(2));
+            let s = v1.add(&v2);
+            let m = v0.add(&scale(&s, w.re));
+            let d = rotate(&v1.substract(&v2), w.im);
+            store(0, v0.add(&s));
+            store(1, m.add(&d));
+            store(2, m.substract(&d));
+        }
+        4 => {
+            let w = &twiddles[length / 4];
+            let (v0, v1, v2, v3) = (v(0), v(1), v(2), v(3));
+            let t0 = v0.add(&v2);
+            let t1 = v0.substract(&v2);
+            let t2 = v1.add(&v3);
+            let t3 = rotate(&v1.substract(&v3), w.im);
+            store(0, t0.add(&t2));
+            store(1, t1.add(&t3));
+            store(2, t0.substract(&t2));
+            store(3, t1.substract(&t3));
+        }
+        5 => {
+            let (w1, w2) = (&twiddles[length / 5], &twiddles[2 * length / 5]);
+            let (v0, v1, v2, v3, v4) = (v(0), v(1), v(2), v(3), v(4));
+            let (s1, d1) = (v1.add(&v4), v1.substract(&v4));
+            let (s2, d2) = (v2.add(&v3), v2.substract(&v3));
+            let m1 = v0.add(&scale(&s1, w1.re)).add(&scale(&s2, w2.re));
+            let m2 = v0.add(&scale(&s1, w2.re)).add(&scale(&s2, w1.re));
+            let r1 = rotate(&d1, w1.im).add(&rotate(&d2, w2.im));
+            let r2 = rotate(&d1, w2.im).substract(&rotate(&d2, w1.im));
+            store(0, v0.add(&s1).add(&s2));
+            store(1, m1.add(&r1));
+            store(2, m2.add(&r2));
+            store(3, m2.substract(&r2));
+            store(4, m1.substract(&r1));
+        }
+        _ => {
+            let root = length / radix;
+            for q in 0..radix {
+                let value = (0..radix).fold(zero(), |acc, r| {
+                    let w = &twiddles[(r * k * stride + r * q * root) % length];
+                    acc.add(&src[j + r * groups].multiply(w))
+                });
+                store(q, value);
+            }
+        }
+    }
+}
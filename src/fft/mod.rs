@@ -0,0 +1,654 @@
+mod bluestein;
+mod decimal;
+mod freq;
+mod mixed_radix;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod plan;
+mod radix2;
+mod real;
+mod shift;
+mod two_dim;
+
+pub use decimal::{fft_decimal, ifft_decimal};
+pub use freq::{
+    bin_to_frequency, fftfreq, find_peaks, frequency_to_bin, interpolate_peak, nearest_bin,
+    rfftfreq, Peak,
+};
+#[cfg(feature = "rayon")]
+pub use parallel::{par_fft_batch, par_fft_in_place, par_ifft_batch, par_ifft_in_place};
+pub use plan::{FftPlan, FftPlanner};
+pub use real::{irfft, rfft};
+pub use shift::{fftshift, fftshift2, ifftshift, ifftshift2};
+pub use two_dim::{fft2, ifft2};
+
+use crate::complex::Complex;
+use num::{FromPrimitive, Num, ToPrimitive};
+use rust_decimal::Decimal;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::ops::Neg;
+use std::sync::Arc;
+
+/// Element types the transforms accept: `f32`, `f64` and `Decimal`. Twiddle factors are
+/// computed in `f64`; see [`fft_decimal`] for full `Decimal` precision. The trait is sealed
+/// because integer types would truncate the twiddles to zero.
+pub trait FftNum:
+    sealed::Sealed
+    + Num
+    + Neg<Output = Self>
+    + FromPrimitive
+    + ToPrimitive
+    + Copy
+    + Send
+    + Sync
+    + 'static
+{
+}
+
+impl FftNum for f32 {}
+impl FftNum for f64 {}
+impl FftNum for Decimal {}
+
+pub(crate) mod sealed {
+    pub trait Sealed {
+        /// Relative precision of the type, for tolerances that must follow it.
+        const EPSILON: f64;
+    }
+
+    impl Sealed for f32 {
+        const EPSILON: f64 = f32::EPSILON as f64;
+    }
+
+    impl Sealed for f64 {
+        const EPSILON: f64 = f64::EPSILON;
+    }
+
+    impl Sealed for rust_decimal::Decimal {
+        const EPSILON: f64 = 1e-28;
+    }
+}
+
+/// Lengths whose prime factors are all at most this go through the mixed-radix passes,
+/// anything with a larger prime factor is handed to Bluestein's algorithm.
+const MAX_RADIX: usize = 31;
+
+/// Scaling applied to the forward and inverse transforms.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Neither direction is scaled, so `ifft(fft(x)) == n * x`.
+    None,
+    /// The inverse transform is scaled by `1/n`.
+    #[default]
+    Backward,
+    /// Both directions are scaled by `1/√n`, which makes the transform unitary.
+    Ortho,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Forward,
+    Inverse,
+}
+
+impl Direction {
+    /// Sign of the exponent of the transform's root of unity.
+    fn sign(&self) -> f64 {
+        match self {
+            Direction::Forward => -1.0,
+            Direction::Inverse => 1.0,
+        }
+    }
+}
+
+pub fn fft<T: FftNum>(x: Vec<Complex<T>>) -> Vec<Complex<T>> {
+    fft_normalized(x, Normalization::Backward)
+}
+
+pub fn ifft<T: FftNum>(x: Vec<Complex<T>>) -> Vec<Complex<T>> {
+    ifft_normalized(x, Normalization::Backward)
+}
+
+pub fn fft_normalized<T: FftNum>(mut x: Vec<Complex<T>>, norm: Normalization) -> Vec<Complex<T>> {
+    transform(&mut x, Direction::Forward);
+    if norm == Normalization::Ortho {
+        normalize(&mut x, norm);
+    }
+    x
+}
+
+pub fn ifft_normalized<T: FftNum>(mut x: Vec<Complex<T>>, norm: Normalization) -> Vec<Complex<T>> {
+    transform(&mut x, Direction::Inverse);
+    normalize(&mut x, norm);
+    x
+}
+
+/// Unnormalized forward transform of `x` in place. Power-of-two lengths need no scratch
+/// space; other lengths allocate one, use [`fft_with_scratch`] to supply it instead.
+pub fn fft_in_place<T: FftNum>(x: &mut [Complex<T>]) {
+    transform(x, Direction::Forward);
+}
+
+/// Inverse transform of `x` in place, scaled by `1/n` like [`ifft`].
+pub fn ifft_in_place<T: FftNum>(x: &mut [Complex<T>]) {
+    transform(x, Direction::Inverse);
+    normalize(x, Normalization::Backward);
+}
+
+/// Unnormalized forward transform of `x` in place using caller-provided scratch space of
+/// at least [`scratch_len`] elements. Once the plan for `x.len()` is cached on the calling
+/// thread this does not allocate.
+///
+/// # Panics
+///
+/// If `scratch` is shorter than `scratch_len(x.len())`.
+pub fn fft_with_scratch<T: FftNum>(x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+    cached_plan(x.len(), Direction::Forward).process_with_scratch(x, scratch);
+}
+
+/// Inverse counterpart of [`fft_with_scratch`], scaled by `1/n` like [`ifft`].
+pub fn ifft_with_scratch<T: FftNum>(x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+    cached_plan(x.len(), Direction::Inverse).process_with_scratch(x, scratch);
+    normalize(x, Normalization::Backward);
+}
+
+/// Unnormalized forward transforms of the consecutive signals of `length` samples that
+/// make up `x`, all sharing one plan and scratch buffer.
+///
+/// # Panics
+///
+/// If `length` is zero or does not divide `x.len()`.
+pub fn fft_batch<T: FftNum>(x: &mut [Complex<T>], length: usize) {
+    check_batch(x, length);
+    let plan = cached_plan(length, Direction::Forward);
+    let mut scratch = vec![zero(); scratch_len(length)];
+    x.chunks_mut(length)
+        .for_each(|signal| plan.process_with_scratch(signal, &mut scratch));
+}
+
+/// Inverse counterpart of [`fft_batch`], each signal scaled by `1/length`.
+///
+/// # Panics
+///
+/// If `length` is zero or does not divide `x.len()`.
+pub fn ifft_batch<T: FftNum>(x: &mut [Complex<T>], length: usize) {
+    check_batch(x, length);
+    let plan = cached_plan(length, Direction::Inverse);
+    let mut scratch = vec![zero(); scratch_len(length)];
+    x.chunks_mut(length).for_each(|signal| {
+        plan.process_with_scratch(signal, &mut scratch);
+        normalize(signal, Normalization::Backward);
+    });
+}
+
+fn check_batch<T>(x: &[T], length: usize) {
+    assert!(
+        length > 0 && x.len().is_multiple_of(length),
+        "batch length must divide the buffer length"
+    );
+}
+
+/// Scratch space the `*_with_scratch` functions need for a transform of `length`.
+pub fn scratch_len(length: usize) -> usize {
+    match length {
+        0 | 1 => 0,
+        _ => match Algorithm::select(length) {
+            Algorithm::Radix2 => 0,
+            Algorithm::MixedRadix(_) => length,
+            Algorithm::Bluestein => bluestein::padded_len(length),
+        },
+    }
+}
+
+fn transform<T: FftNum>(x: &mut [Complex<T>], direction: Direction) {
+    cached_plan(x.len(), direction).process(x);
+}
+
+thread_local! {
+    /// One planner per element type, so the free functions only pay for planning once per
+    /// length on each thread.
+    static PLANNERS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn cached_plan<T: FftNum>(length: usize, direction: Direction) -> Arc<FftPlan<T>> {
+    PLANNERS.with(|planners| {
+        planners
+            .borrow_mut()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(FftPlanner::<T>::new()))
+            .downcast_mut::<FftPlanner<T>>()
+            .unwrap()
+            .plan(length, direction)
+    })
+}
+
+/// Applies the scaling `norm` prescribes for an inverse transform; the forward transform
+/// only needs it for [`Normalization::Ortho`], where both factors are the same.
+fn normalize<T: Num + FromPrimitive + Copy>(x: &mut [Complex<T>], norm: Normalization) {
+    let length = x.len();
+    if length <= 1 {
+        return;
+    }
+
+    match norm {
+        Normalization::None => {}
+        Normalization::Backward => {
+            let n = T::from_usize(length).unwrap();
+            x.iter_mut().for_each(|c| {
+                c.re = c.re / n;
+                c.im = c.im / n;
+            });
+        }
+        Normalization::Ortho => {
+            let factor = T::from_f64(1.0 / (length as f64).sqrt()).unwrap();
+            x.iter_mut().for_each(|c| {
+                c.re = c.re * factor;
+                c.im = c.im * factor;
+            });
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Algorithm {
+    Radix2,
+    MixedRadix(Vec<usize>),
+    Bluestein,
+}
+
+impl Algorithm {
+    fn select(length: usize) -> Algorithm {
+        if length.is_power_of_two() {
+            return Algorithm::Radix2;
+        }
+
+        let factors = mixed_radix::factorize(length);
+        match factors.iter().all(|&f| f <= MAX_RADIX) {
+            true => Algorithm::MixedRadix(factors),
+            false => Algorithm::Bluestein,
+        }
+    }
+}
+
+/// First `count` powers of `e^(∓2πi/length)`, the sign following `direction`.
+fn twiddles<T: FromPrimitive>(
+    count: usize,
+    length: usize,
+    direction: Direction,
+) -> Vec<Complex<T>> {
+    (0..count)
+        .map(|k| {
+            let (sin, cos) = (direction.sign() * TAU * k as f64 / length as f64).sin_cos();
+            Complex {
+                re: T::from_f64(cos).unwrap(),
+                im: T::from_f64(sin).unwrap(),
+            }
+        })
+        .collect()
+}
+
+#[inline]
+pub(crate) fn zero<T: Num>() -> Complex<T> {
+    Complex {
+        re: T::zero(),
+        im: T::zero(),
+    }
+}
+
+#[inline]
+fn scale<T: Num + Copy>(c: &Complex<T>, factor: T) -> Complex<T> {
+    Complex {
+        re: c.re * factor,
+        im: c.im * factor,
+    }
+}
+
+/// Multiplies `c` by `i * factor`.
+#[inline]
+fn rotate<T: Num + Copy>(c: &Complex<T>, factor: T) -> Complex<T> {
+    Complex {
+        re: T::zero() - c.im * factor,
+        im: c.re * factor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    #[rustfmt::skip]
+    fn fft_test() {
+        let x = vec![
+            Complex { re: dec!(-2.0), im: dec!(4.0)},
+            Complex { re: dec!(5.0), im: dec!(-5.0)},
+            Complex { re: dec!(10.0), im: dec!(4.0)},
+            Complex { re: dec!(-1.0), im: dec!(-9.0)},
+            Complex { re: dec!(-8.0), im: dec!(3.0)},
+            Complex { re: dec!(9.0), im: dec!(-5.0)},
+            Complex { re: dec!(-8.0), im: dec!(-4.0)},
+            Complex { re: dec!(3.0), im: dec!(-7.0)},
+            Complex { re: dec!(-10.0), im: dec!(1.0)},
+            Complex { re: dec!(-8.0), im: dec!(1.0)},
+        ];
+        let res = vec![
+            Complex { re: dec!(-10.0), im: dec!(-17.0) }, 
+            Complex { re: dec!(0.438028706609127207686783084), im: dec!(-9.795806641746903646163790450) }, 
+            Complex { re: dec!(-9.914165332234708315373566167), im: dec!(-20.116478800684224607672419101) }, 
+            Complex { re: dec!(-13.035915674137241776939650750), im: dec!(-10.021476573746968938491371349) }, 
+            Complex { re: dec!(10.592866020451171869252867666), im: dec!(14.830046144997133384655161799) }, 
+            Complex { re: dec!(-25.999999999999822084726981713), im: dec!(33.000000000000239569080499873) }, 
+            Complex { re: dec!(35.385847742996760553879301499), im: dec!(-15.538250077575895023017257302) }, 
+            Complex { re: dec!(-13.762458202143288146192518416), im: dec!(-4.103135223419962323146533148) }, 
+            Complex { re: dec!(8.935451568518820038505735332), im: dec!(32.824682733009841069310323598) }, 
+            Complex { re: dec!(-2.639654830564728830818952248), im: dec!(35.920418439292635084525885953) }
+        ];
+      
+        // The reference vectors were produced with Decimal sin/cos and are only accurate to ~1e-10.
+        let fft_x = fft(x);
+        for i in 0..fft_x.len() {
+            assert!((fft_x[i].re - res[i].re).abs().to_f64().unwrap() < 1e-9);
+            assert!((fft_x[i].im - res[i].im).abs().to_f64().unwrap() < 1e-9);
+        }
+    }
+
+    /// Direct O(n²) evaluation of the DFT, the reference every fast path is checked against.
+    fn dft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let length = x.len();
+        let twiddles = twiddles::<f64>(length, length, Direction::Forward);
+        (0..length)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .fold(Complex { re: 0.0, im: 0.0 }, |acc, (n, xn)| {
+                        acc.add(&xn.multiply(&twiddles[(n * k) % length]))
+                    })
+            })
+            .collect()
+    }
+
+    fn assert_close(a: &[Complex<f64>], b: &[Complex<f64>], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x.re - y.re).abs() < tol, "{:?} != {:?}", x, y);
+            assert!((x.im - y.im).abs() < tol, "{:?} != {:?}", x, y);
+        }
+    }
+
+    fn signal(length: usize) -> Vec<Complex<f64>> {
+        (0..length)
+            .map(|i| Complex {
+                re: ((i * 7 + 3) % 11) as f64 - 5.0,
+                im: ((i * 5 + 1) % 13) as f64 - 6.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn radix2_matches_dft_test() {
+        for length in [2, 4, 8, 16, 64, 256] {
+            let x = signal(length);
+            assert_close(&fft(x.clone()), &dft(&x), 1e-9);
+        }
+    }
+
+    #[test]
+    fn factorize_test() {
+        assert_eq!(mixed_radix::factorize(10), vec![2, 5]);
+        assert_eq!(mixed_radix::factorize(48), vec![4, 4, 3]);
+        assert_eq!(
+            mixed_radix::factorize(2 * 9 * 7 * 49),
+            vec![2, 3, 3, 7, 7, 7]
+        );
+        assert_eq!(mixed_radix::factorize(1009), vec![1009]);
+    }
+
+    #[test]
+    fn select_algorithm_test() {
+        assert_eq!(Algorithm::select(1024), Algorithm::Radix2);
+        assert_eq!(Algorithm::select(10), Algorithm::MixedRadix(vec![2, 5]));
+        assert_eq!(Algorithm::select(2 * 37), Algorithm::Bluestein);
+        assert_eq!(Algorithm::select(1009), Algorithm::Bluestein);
+    }
+
+    #[test]
+    fn mixed_radix_matches_dft_test() {
+        for length in [
+            3, 5, 6, 7, 9, 10, 12, 15, 20, 24, 25, 30, 36, 45, 49, 60, 77, 100, 120, 143, 961,
+        ] {
+            let x = signal(length);
+            assert_close(&fft(x.clone()), &dft(&x), 1e-8);
+        }
+    }
+
+    #[test]
+    fn bluestein_matches_dft_test() {
+        for length in [37, 74, 101, 127, 2 * 3 * 41, 1009] {
+            let x = signal(length);
+            assert_close(&fft(x.clone()), &dft(&x), 1e-7);
+        }
+    }
+
+    #[test]
+    fn bluestein_f32_test() {
+        let x: Vec<Complex<f32>> = signal(53)
+            .iter()
+            .map(|c| Complex {
+                re: c.re as f32,
+                im: c.im as f32,
+            })
+            .collect();
+        let expected = dft(&signal(53));
+        for (a, b) in fft(x).iter().zip(expected.iter()) {
+            assert!((a.re as f64 - b.re).abs() < 1e-3 && (a.im as f64 - b.im).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn radix2_impulse_test() {
+        let mut x = vec![Complex { re: 0.0, im: 0.0 }; 16];
+        x[1] = Complex { re: 1.0, im: 0.0 };
+        let expected: Vec<Complex<f64>> = twiddles(16, 16, Direction::Forward);
+        assert_close(&fft(x), &expected, 1e-12);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn radix2_decimal_test() {
+        let x = vec![
+            Complex { re: dec!(1.0), im: dec!(0.0) },
+            Complex { re: dec!(2.0), im: dec!(-1.0) },
+            Complex { re: dec!(0.0), im: dec!(-1.0) },
+            Complex { re: dec!(-1.0), im: dec!(2.0) },
+        ];
+        let res = [
+            Complex { re: dec!(2.0), im: dec!(0.0) },
+            Complex { re: dec!(-2.0), im: dec!(-2.0) },
+            Complex { re: dec!(0.0), im: dec!(-2.0) },
+            Complex { re: dec!(4.0), im: dec!(4.0) },
+        ];
+
+        let fft_x = fft(x);
+        for i in 0..fft_x.len() {
+            assert!((fft_x[i].re - res[i].re).abs().to_f64().unwrap() < 1e-15);
+            assert!((fft_x[i].im - res[i].im).abs().to_f64().unwrap() < 1e-15);
+        }
+    }
+
+    /// Small linear congruential generator so the round-trip tests cover many inputs
+    /// without pulling in a dependency.
+    fn random_signal(length: usize, seed: u64) -> Vec<Complex<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        };
+        (0..length)
+            .map(|_| Complex {
+                re: next(),
+                im: next(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ifft_matches_conjugate_dft_test() {
+        let x = signal(12);
+        let conj: Vec<Complex<f64>> = x
+            .iter()
+            .map(|c| Complex {
+                re: c.re,
+                im: -c.im,
+            })
+            .collect();
+        let expected: Vec<Complex<f64>> = dft(&conj)
+            .iter()
+            .map(|c| Complex {
+                re: c.re / 12.0,
+                im: -c.im / 12.0,
+            })
+            .collect();
+        assert_close(&ifft(x), &expected, 1e-12);
+    }
+
+    #[test]
+    fn round_trip_f64_test() {
+        for length in 1..=130 {
+            let x = random_signal(length, length as u64);
+            assert_close(&ifft(fft(x.clone())), &x, 1e-12);
+            let ortho = ifft_normalized(
+                fft_normalized(x.clone(), Normalization::Ortho),
+                Normalization::Ortho,
+            );
+            assert_close(&ortho, &x, 1e-12);
+        }
+    }
+
+    #[test]
+    fn round_trip_f32_test() {
+        for length in [1, 2, 7, 16, 30, 64, 97, 100] {
+            let x: Vec<Complex<f32>> = random_signal(length, 7)
+                .iter()
+                .map(|c| Complex {
+                    re: c.re as f32,
+                    im: c.im as f32,
+                })
+                .collect();
+            for (a, b) in ifft(fft(x.clone())).iter().zip(x.iter()) {
+                assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_decimal_test() {
+        for length in [4, 10, 37] {
+            let x: Vec<Complex<Decimal>> = signal(length)
+                .iter()
+                .map(|c| Complex {
+                    re: Decimal::from_f64(c.re).unwrap(),
+                    im: Decimal::from_f64(c.im).unwrap(),
+                })
+                .collect();
+            let y = ifft(fft(x.clone()));
+            for (a, b) in y.iter().zip(x.iter()) {
+                assert!((a.re - b.re).abs() < dec!(1e-12) && (a.im - b.im).abs() < dec!(1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn normalization_none_test() {
+        let x = random_signal(10, 3);
+        let y = ifft_normalized(
+            fft_normalized(x.clone(), Normalization::None),
+            Normalization::None,
+        );
+        let scaled: Vec<Complex<f64>> = x
+            .iter()
+            .map(|c| Complex {
+                re: c.re * 10.0,
+                im: c.im * 10.0,
+            })
+            .collect();
+        assert_close(&y, &scaled, 1e-12);
+    }
+
+    #[test]
+    fn ortho_preserves_energy_test() {
+        let x = random_signal(45, 11);
+        let y = fft_normalized(x.clone(), Normalization::Ortho);
+        let energy = |v: &[Complex<f64>]| v.iter().map(|c| c.re * c.re + c.im * c.im).sum::<f64>();
+        assert!((energy(&x) - energy(&y)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn in_place_matches_fft_test() {
+        for length in [0, 1, 8, 18, 41] {
+            let x = random_signal(length, 5);
+            let mut y = x.clone();
+            fft_in_place(&mut y);
+            assert_close(&y, &fft(x.clone()), 1e-12);
+            ifft_in_place(&mut y);
+            assert_close(&y, &x, 1e-12);
+        }
+    }
+
+    #[test]
+    fn with_scratch_test() {
+        let mut scratch = vec![Complex { re: 0.0, im: 0.0 }; 256];
+        for length in [16, 18, 41, 100] {
+            assert!(scratch_len(length) <= scratch.len());
+            let x = random_signal(length, 9);
+            let mut y = x.clone();
+            fft_with_scratch(&mut y, &mut scratch);
+            assert_close(&y, &fft(x.clone()), 1e-12);
+            ifft_with_scratch(&mut y, &mut scratch);
+            assert_close(&y, &x, 1e-12);
+        }
+        assert_eq!(scratch_len(16), 0);
+        assert_eq!(scratch_len(18), 18);
+        assert_eq!(scratch_len(41), 128);
+    }
+
+    #[test]
+    fn batch_test() {
+        for length in [8, 12, 41] {
+            let x = random_signal(3 * length, 4);
+            let mut y = x.clone();
+            fft_batch(&mut y, length);
+            for (signal, spectrum) in x.chunks(length).zip(y.chunks(length)) {
+                assert_close(spectrum, &dft(signal), 1e-12);
+            }
+            ifft_batch(&mut y, length);
+            assert_close(&y, &x, 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "batch length must divide the buffer length")]
+    fn batch_length_mismatch_test() {
+        fft_batch(&mut random_signal(10, 1), 4);
+    }
+
+    #[test]
+    fn ring_buffer_frames_test() {
+        let ring = random_signal(64, 21);
+        let mut frame = vec![Complex { re: 0.0, im: 0.0 }; 12];
+        let mut scratch = vec![Complex { re: 0.0, im: 0.0 }; scratch_len(12)];
+        for start in (0..64).step_by(12) {
+            for (i, slot) in frame.iter_mut().enumerate() {
+                *slot = ring[(start + i) % 64];
+            }
+            let expected = dft(&frame);
+            fft_with_scratch(&mut frame, &mut scratch);
+            assert_close(&frame, &expected, 1e-12);
+        }
+    }
+}
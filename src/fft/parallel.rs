@@ -0,0 +1,134 @@
+use super::{cached_plan, scratch_len, zero, Direction, FftNum};
+use crate::complex::Complex;
+use rayon::prelude::*;
+
+/// Smallest number of elements worth handing to a rayon task.
+pub(super) const MIN_CHUNK: usize = 1024;
+
+/// [`fft_in_place`](super::fft_in_place) with each stage split across the rayon thread pool.
+/// Worth it for transforms of tens of thousands of points and up; the result is
+/// bit-identical to the serial function.
+pub fn par_fft_in_place<T: FftNum>(x: &mut [Complex<T>]) {
+    cached_plan(x.len(), Direction::Forward).par_process(x);
+}
+
+/// Parallel [`ifft_in_place`](super::ifft_in_place), scaled by `1/n`.
+pub fn par_ifft_in_place<T: FftNum>(x: &mut [Complex<T>]) {
+    cached_plan(x.len(), Direction::Inverse).par_process(x);
+    let n = T::from_usize(x.len()).unwrap();
+    x.par_iter_mut().with_min_len(MIN_CHUNK).for_each(|c| {
+        c.re = c.re / n;
+        c.im = c.im / n;
+    });
+}
+
+/// [`fft_batch`](super::fft_batch) with the signals distributed over the rayon thread pool.
+///
+/// # Panics
+///
+/// If `length` is zero or does not divide `x.len()`.
+pub fn par_fft_batch<T: FftNum>(x: &mut [Complex<T>], length: usize) {
+    batch(x, length, Direction::Forward);
+}
+
+/// [`ifft_batch`](super::ifft_batch) with the signals distributed over the rayon thread pool.
+///
+/// # Panics
+///
+/// If `length` is zero or does not divide `x.len()`.
+pub fn par_ifft_batch<T: FftNum>(x: &mut [Complex<T>], length: usize) {
+    batch(x, length, Direction::Inverse);
+}
+
+fn batch<T: FftNum>(x: &mut [Complex<T>], length: usize, direction: Direction) {
+    super::check_batch(x, length);
+    let plan = cached_plan(length, direction);
+    x.par_chunks_mut(length)
+        .with_min_len(MIN_CHUNK / length + 1)
+        .for_each_init(
+            || vec![zero(); scratch_len(length)],
+            |scratch, signal| {
+                plan.process_with_scratch(signal, scratch);
+                if direction == Direction::Inverse {
+                    super::normalize(signal, super::Normalization::Backward);
+                }
+            },
+        );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::{fft_batch, fft_in_place, ifft_batch, ifft_in_place};
+
+    fn random_signal(length: usize, seed: u64) -> Vec<Complex<f64>> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        };
+        (0..length)
+            .map(|_| Complex {
+                re: next(),
+                im: next(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn single_transform_bit_identical_test() {
+        // Power of two, mixed radix with each specialised butterfly, a generic radix and
+        // Bluestein, large enough for both splitting strategies to kick in.
+        for length in [
+            1 << 16,
+            3 * 5 * 4096,
+            7 * 11 * 1024,
+            25 * 27 * 49,
+            40009,
+            17,
+        ] {
+            let x = random_signal(length, length as u64);
+            let (mut serial, mut parallel) = (x.clone(), x.clone());
+            fft_in_place(&mut serial);
+            par_fft_in_place(&mut parallel);
+            assert!(serial == parallel, "forward {}", length);
+            ifft_in_place(&mut serial);
+            par_ifft_in_place(&mut parallel);
+            assert!(serial == parallel, "inverse {}", length);
+        }
+    }
+
+    #[test]
+    fn batch_bit_identical_test() {
+        for (length, count) in [(64, 500), (60, 333), (37, 20), (1, 5)] {
+            let x = random_signal(length * count, 9);
+            let (mut serial, mut parallel) = (x.clone(), x.clone());
+            fft_batch(&mut serial, length);
+            par_fft_batch(&mut parallel, length);
+            assert!(serial == parallel, "forward {}", length);
+            ifft_batch(&mut serial, length);
+            par_ifft_batch(&mut parallel, length);
+            assert!(serial == parallel, "inverse {}", length);
+        }
+    }
+
+    #[test]
+    fn batch_matches_single_test() {
+        let x = random_signal(48 * 3, 2);
+        let mut batched = x.clone();
+        par_fft_batch(&mut batched, 48);
+        for (signal, expected) in x.chunks(48).zip(batched.chunks(48)) {
+            let mut single = signal.to_vec();
+            fft_in_place(&mut single);
+            assert!(single == expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "batch length must divide the buffer length")]
+    fn batch_length_mismatch_test() {
+        par_fft_batch(&mut random_signal(10, 1), 4);
+    }
+}
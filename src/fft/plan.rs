@@ -0,0 +1,273 @@
+use super::bluestein::{self, Bluestein};
+use super::mixed_radix::MixedRadix;
+use super::radix2::Radix2;
+use super::{zero, Algorithm, Direction, FftNum};
+use crate::complex::Complex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A transform of a fixed length and direction with all twiddle factors and permutation
+/// tables computed up front, so it can be applied to any number of buffers.
+#[derive(Debug, Clone)]
+pub struct FftPlan<T> {
+    length: usize,
+    direction: Direction,
+    kernel: Kernel<T>,
+}
+
+#[derive(Debug, Clone)]
+enum Kernel<T> {
+    Identity,
+    Radix2(Radix2<T>),
+    MixedRadix(MixedRadix<T>),
+    Bluestein(Bluestein<T>),
+}
+
+impl<T: FftNum> FftPlan<T> {
+    pub fn new(length: usize, direction: Direction) -> Self {
+        let kernel = match length {
+            0 | 1 => Kernel::Identity,
+            _ => match Algorithm::select(length) {
+                Algorithm::Radix2 => Kernel::Radix2(Radix2::new(length, direction)),
+                Algorithm::MixedRadix(factors) => {
+                    Kernel::MixedRadix(MixedRadix::new(length, factors, direction))
+                }
+                Algorithm::Bluestein => Kernel::Bluestein(Bluestein::new(length, direction)),
+            },
+        };
+
+        FftPlan {
+            length,
+            direction,
+            kernel,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Number of elements [`FftPlan::process_with_scratch`] needs in its scratch buffer.
+    pub fn scratch_len(&self) -> usize {
+        match &self.kernel {
+            Kernel::Identity | Kernel::Radix2(_) => 0,
+            Kernel::MixedRadix(_) => self.length,
+            Kernel::Bluestein(_) => bluestein::padded_len(self.length),
+        }
+    }
+
+    /// Transforms `x` in place without any normalization, allocating a scratch buffer if
+    /// the length needs one.
+    ///
+    /// # Panics
+    ///
+    /// If `x.len()` differs from the length the plan was built for.
+    pub fn process(&self, x: &mut [Complex<T>]) {
+        let mut scratch = vec![zero(); self.scratch_len()];
+        self.process_with_scratch(x, &mut scratch);
+    }
+
+    /// Transforms `x` in place without any normalization or allocation.
+    ///
+    /// # Panics
+    ///
+    /// If `x.len()` differs from the length the plan was built for, or `scratch` is
+    /// shorter than [`FftPlan::scratch_len`].
+    pub fn process_with_scratch(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(
+            x.len(),
+            self.length,
+            "buffer length does not match the plan"
+        );
+        assert!(
+            scratch.len() >= self.scratch_len(),
+            "scratch buffer is shorter than the plan requires"
+        );
+
+        match &self.kernel {
+            Kernel::Identity => {}
+            Kernel::Radix2(kernel) => kernel.process(x),
+            Kernel::MixedRadix(kernel) => kernel.process(x, scratch),
+            Kernel::Bluestein(kernel) => kernel.process(x, scratch),
+        }
+    }
+
+    /// Like [`FftPlan::process`], but each stage of the transform is split across the rayon
+    /// thread pool. The arithmetic is unchanged, so the result is bit-identical.
+    #[cfg(feature = "rayon")]
+    pub fn par_process(&self, x: &mut [Complex<T>]) {
+        let mut scratch = vec![zero(); self.scratch_len()];
+        self.par_process_with_scratch(x, &mut scratch);
+    }
+
+    /// Parallel counterpart of [`FftPlan::process_with_scratch`], with the same panics.
+    #[cfg(feature = "rayon")]
+    pub fn par_process_with_scratch(&self, x: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        assert_eq!(
+            x.len(),
+            self.length,
+            "buffer length does not match the plan"
+        );
+        assert!(
+            scratch.len() >= self.scratch_len(),
+            "scratch buffer is shorter than the plan requires"
+        );
+
+        match &self.kernel {
+            Kernel::Identity => {}
+            Kernel::Radix2(kernel) => kernel.process_parallel(x),
+            Kernel::MixedRadix(kernel) => kernel.process_parallel(x, scratch),
+            Kernel::Bluestein(kernel) => kernel.process_parallel(x, scratch),
+        }
+    }
+}
+
+/// Builds [`FftPlan`]s and caches them by length and direction, handing out shared
+/// references that can be sent to other threads.
+#[derive(Debug, Clone)]
+pub struct FftPlanner<T> {
+    cache: HashMap<(usize, Direction), Arc<FftPlan<T>>>,
+}
+
+impl<T: FftNum> Default for FftPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FftNum> FftPlanner<T> {
+    pub fn new() -> Self {
+        FftPlanner {
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn plan(&mut self, length: usize, direction: Direction) -> Arc<FftPlan<T>> {
+        self.cache
+            .entry((length, direction))
+            .or_insert_with(|| Arc::new(FftPlan::new(length, direction)))
+            .clone()
+    }
+
+    pub fn plan_forward(&mut self, length: usize) -> Arc<FftPlan<T>> {
+        self.plan(length, Direction::Forward)
+    }
+
+    pub fn plan_inverse(&mut self, length: usize) -> Arc<FftPlan<T>> {
+        self.plan(length, Direction::Inverse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::{fft, ifft_normalized, Normalization};
+    use std::thread;
+
+    fn signal(length: usize, offset: usize) -> Vec<Complex<f64>> {
+        (0..length)
+            .map(|i| Complex {
+                re: ((i * 7 + offset) % 11) as f64 - 5.0,
+                im: ((i * 5 + offset) % 13) as f64 - 6.0,
+            })
+            .collect()
+    }
+
+    fn assert_close(a: &[Complex<f64>], b: &[Complex<f64>]) {
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x.re - y.re).abs() < 1e-9 && (x.im - y.im).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn planner_caches_plans_test() {
+        let mut planner = FftPlanner::<f64>::new();
+        let a = planner.plan_forward(60);
+        let b = planner.plan(60, Direction::Forward);
+        let c = planner.plan_inverse(60);
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(c.direction(), Direction::Inverse);
+        assert_eq!(a.len(), 60);
+    }
+
+    #[test]
+    fn plan_reuse_test() {
+        let mut planner = FftPlanner::new();
+        for length in [16, 45, 67] {
+            let forward = planner.plan_forward(length);
+            let inverse = planner.plan_inverse(length);
+            for offset in 0..4 {
+                let x = signal(length, offset);
+                let mut y = x.clone();
+                forward.process(&mut y);
+                assert_close(&y, &fft(x.clone()));
+                inverse.process(&mut y);
+                assert_close(&ifft_normalized(fft(x.clone()), Normalization::None), &y);
+            }
+        }
+    }
+
+    #[test]
+    fn plan_shared_across_threads_test() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<FftPlan<f64>>();
+        assert_send_sync::<FftPlanner<f32>>();
+
+        let plan = FftPlanner::new().plan_forward(30);
+        thread::scope(|s| {
+            for offset in 0..4 {
+                let plan = Arc::clone(&plan);
+                s.spawn(move || {
+                    let x = signal(30, offset);
+                    let mut y = x.clone();
+                    plan.process(&mut y);
+                    assert_close(&y, &fft(x));
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn process_with_scratch_test() {
+        for length in [32, 36, 71] {
+            let plan = FftPlan::new(length, Direction::Forward);
+            let mut scratch = vec![Complex { re: 0.0, im: 0.0 }; plan.scratch_len() + 3];
+            let x = signal(length, 1);
+            let mut y = x.clone();
+            plan.process_with_scratch(&mut y, &mut scratch);
+            assert_close(&y, &fft(x));
+        }
+        assert_eq!(FftPlan::<f64>::new(32, Direction::Forward).scratch_len(), 0);
+        assert_eq!(
+            FftPlan::<f64>::new(36, Direction::Forward).scratch_len(),
+            36
+        );
+        assert_eq!(
+            FftPlan::<f64>::new(71, Direction::Forward).scratch_len(),
+            256
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "scratch buffer is shorter than the plan requires")]
+    fn short_scratch_test() {
+        let plan = FftPlan::<f64>::new(12, Direction::Forward);
+        plan.process_with_scratch(&mut signal(12, 0), &mut signal(11, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer length does not match the plan")]
+    fn plan_length_mismatch_test() {
+        let plan = FftPlan::<f64>::new(8, Direction::Forward);
+        plan.process(&mut signal(7, 0));
+    }
+}
@@ -0,0 +1,102 @@
+use super::{twiddles, Direction, FftNum};
+use crate::complex::Complex;
+
+/// Iterative radix-2 decimation-in-time transform for power-of-two lengths.
+#[derive(Debug, Clone)]
+pub(super) struct Radix2<T> {
+    twiddles: Vec<Complex<T>>,
+    swaps: Vec<(usize, usize)>,
+}
+
+impl<T: FftNum> Radix2<T> {
+    pub(super) fn new(length: usize, direction: Direction) -> Self {
+        let bits = length.trailing_zeros();
+        let swaps = (0..length)
+            .map(|i| (i, i.reverse_bits() >> (usize::BITS - bits)))
+            .filter(|(i, j)| i < j)
+            .collect();
+
+        Radix2 {
+            twiddles: twiddles(length / 2, length, direction),
+            swaps,
+        }
+    }
+
+    pub(super) fn process(&self, x: &mut [Complex<T>]) {
+        let length = x.len();
+        for &(i, j) in &self.swaps {
+            x.swap(i, j);
+        }
+
+        let mut size = 2;
+        while size <= length {
+            let stride = length / size;
+            for block in x.chunks_mut(size) {
+                let (lo, hi) = block.split_at_mut(size / 2);
+                butterflies(lo, hi, 0, stride, &self.twiddles);
+            }
+            size *= 2;
+        }
+    }
+
+    /// [`Radix2::process`] with every stage spread over the rayon pool: whole blocks while
+    /// there are enough of them, runs of butterflies within a block for the last stages.
+    /// Every element goes through the same operations, so the result is bit-identical.
+    #[cfg(feature = "rayon")]
+    pub(super) fn process_parallel(&self, x: &mut [Complex<T>]) {
+        use super::parallel::MIN_CHUNK;
+        use rayon::prelude::*;
+
+        let length = x.len();
+        for &(i, j) in &self.swaps {
+            x.swap(i, j);
+        }
+
+        let threads = rayon::current_num_threads();
+        let mut size = 2;
+        while size <= length {
+            let stride = length / size;
+            let half = size / 2;
+            match length / size >= 2 * threads {
+                true => x
+                    .par_chunks_mut(size)
+                    .with_min_len(MIN_CHUNK / size + 1)
+                    .for_each(|block| {
+                        let (lo, hi) = block.split_at_mut(half);
+                        butterflies(lo, hi, 0, stride, &self.twiddles);
+                    }),
+                false => {
+                    let run = (half / (4 * threads)).max(MIN_CHUNK);
+                    for block in x.chunks_mut(size) {
+                        let (lo, hi) = block.split_at_mut(half);
+                        lo.par_chunks_mut(run)
+                            .zip(hi.par_chunks_mut(run))
+                            .enumerate()
+                            .for_each(|(i, (lo, hi))| {
+                                butterflies(lo, hi, i * run, stride, &self.twiddles)
+                            });
+                    }
+                }
+            }
+            size *= 2;
+        }
+    }
+}
+
+/// Butterflies `offset..offset + lo.len()` of one block, `lo` and `hi` being the matching
+/// runs of its two halves.
+#[inline]
+fn butterflies<T: FftNum>(
+    lo: &mut [Complex<T>],
+    hi: &mut [Complex<T>],
+    offset: usize,
+    stride: usize,
+    twiddles: &[Complex<T>],
+) {
+    for (k, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+        let t = v.multiply(&twiddles[(offset + k) * stride]);
+        let a = u.add(&t);
+        *v = u.substract(&t);
+        *u = a;
+    }
+}
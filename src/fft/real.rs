@@ -0,0 +1,194 @@
+use super::{fft_in_place, ifft_in_place, rotate, scale, twiddles, Direction, FftNum};
+use crate::complex::Complex;
+
+/// Forward transform of a real signal, returning only the `n/2 + 1` non-redundant bins.
+///
+/// Even lengths pack the samples pairwise into a complex signal of half the length, so the
+/// work is a single `n/2`-point FFT plus a linear untangling pass. Odd lengths fall back to
+/// a full complex transform.
+pub fn rfft<T: FftNum>(x: &[T]) -> Vec<Complex<T>> {
+    let length = x.len();
+    let bins = length / 2 + 1;
+    if length == 0 {
+        return Vec::new();
+    }
+
+    if length % 2 == 1 {
+        let mut full: Vec<Complex<T>> = x.iter().map(|&re| Complex { re, im: T::zero() }).collect();
+        fft_in_place(&mut full);
+        full.truncate(bins);
+        return full;
+    }
+
+    let half = length / 2;
+    let mut z: Vec<Complex<T>> = x
+        .chunks_exact(2)
+        .map(|pair| Complex {
+            re: pair[0],
+            im: pair[1],
+        })
+        .collect();
+    fft_in_place(&mut z);
+
+    let twiddles = twiddles::<T>(bins, length, Direction::Forward);
+    let one_half = T::from_f64(0.5).unwrap();
+    (0..bins)
+        .map(|k| {
+            let zk = &z[k % half];
+            let zc = z[(half - k) % half].conj();
+            let even = scale(&zk.add(&zc), one_half);
+            // (Z[k] - conj(Z[n/2 - k])) / 2i
+            let odd = rotate(&zk.substract(&zc), T::zero() - one_half);
+            even.add(&twiddles[k].multiply(&odd))
+        })
+        .collect()
+}
+
+/// Inverse of [`rfft`]: rebuilds a real signal of `length` samples from its `length/2 + 1`
+/// non-redundant bins, scaled by `1/length` like [`super::ifft`]. The imaginary parts of
+/// the bins that must be real for a real signal are ignored.
+///
+/// # Panics
+///
+/// If `x.len()` is not `length / 2 + 1`.
+pub fn irfft<T: FftNum>(x: &[Complex<T>], length: usize) -> Vec<T> {
+    if length == 0 {
+        return Vec::new();
+    }
+    assert_eq!(
+        x.len(),
+        length / 2 + 1,
+        "spectrum must hold length / 2 + 1 bins"
+    );
+
+    if length % 2 == 1 {
+        let mut full: Vec<Complex<T>> = x.to_vec();
+        full.extend(x[1..].iter().rev().map(Complex::conj));
+        ifft_in_place(&mut full);
+        return full.into_iter().map(|c| c.re).collect();
+    }
+
+    let half = length / 2;
+    let twiddles = twiddles::<T>(half, length, Direction::Inverse);
+    let one_half = T::from_f64(0.5).unwrap();
+    // The packed untangling pairs bin 0 with bin n/2, so their imaginary parts would leak
+    // into every sample; drop them, as the full-length inverse does implicitly.
+    let bin = |k: usize| match k == 0 || k == half {
+        true => Complex {
+            re: x[k].re,
+            im: T::zero(),
+        },
+        false => x[k],
+    };
+    let mut z: Vec<Complex<T>> = (0..half)
+        .map(|k| {
+            let (xk, xc) = (bin(k), bin(half - k).conj());
+            let even = scale(&xk.add(&xc), one_half);
+            let odd = scale(&xk.substract(&xc), one_half).multiply(&twiddles[k]);
+            even.add(&rotate(&odd, T::one()))
+        })
+        .collect();
+    ifft_in_place(&mut z);
+
+    z.into_iter().flat_map(|c| [c.re, c.im]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn signal(length: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| ((i * 7 + 3) % 11) as f64 - 5.0 + 0.25 * (i % 3) as f64)
+            .collect()
+    }
+
+    fn complex_fft(x: &[f64]) -> Vec<Complex<f64>> {
+        fft(x.iter().map(|&re| Complex { re, im: 0.0 }).collect())
+    }
+
+    #[test]
+    fn rfft_matches_complex_fft_test() {
+        for length in 1..=70 {
+            let x = signal(length);
+            let res = rfft(&x);
+            let expected = complex_fft(&x);
+            assert_eq!(res.len(), length / 2 + 1);
+            for (a, b) in res.iter().zip(expected.iter()) {
+                assert!((a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn irfft_round_trip_test() {
+        for length in 1..=70 {
+            let x = signal(length);
+            let y = irfft(&rfft(&x), length);
+            assert_eq!(y.len(), length);
+            for (a, b) in x.iter().zip(y.iter()) {
+                assert!((a - b).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn irfft_ignores_edge_imaginary_parts_test() {
+        for length in [8, 9, 30] {
+            let x = signal(length);
+            let mut spectrum = rfft(&x);
+            spectrum[0].im = 3.5;
+            if length % 2 == 0 {
+                spectrum[length / 2].im = -2.0;
+            }
+            let res = irfft(&spectrum, length);
+            for (a, b) in res.iter().zip(x.iter()) {
+                assert!((a - b).abs() < 1e-12, "{} != {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn rfft_f32_test() {
+        let x: Vec<f32> = signal(48).iter().map(|&v| v as f32).collect();
+        let y = irfft(&rfft(&x), 48);
+        assert!(x.iter().zip(y.iter()).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+
+    #[test]
+    fn rfft_decimal_test() {
+        let x = [dec!(1.0), dec!(2.0), dec!(0.0), dec!(-1.0)];
+        let res = rfft(&x);
+        let expected = [
+            Complex {
+                re: dec!(2.0),
+                im: dec!(0.0),
+            },
+            Complex {
+                re: dec!(1.0),
+                im: dec!(-3.0),
+            },
+            Complex {
+                re: dec!(0.0),
+                im: dec!(0.0),
+            },
+        ];
+        for (a, b) in res.iter().zip(expected.iter()) {
+            assert!((a.re - b.re).abs() < dec!(1e-15) && (a.im - b.im).abs() < dec!(1e-15));
+        }
+        let back: Vec<Decimal> = irfft(&res, 4);
+        assert!(back
+            .iter()
+            .zip(x.iter())
+            .all(|(a, b)| (*a - *b).abs() < dec!(1e-15)));
+    }
+
+    #[test]
+    #[should_panic(expected = "spectrum must hold length / 2 + 1 bins")]
+    fn irfft_wrong_bins_test() {
+        irfft(&rfft(&signal(8)), 10);
+    }
+}
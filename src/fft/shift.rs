@@ -0,0 +1,58 @@
+use crate::matrix::Matrix2D;
+
+/// Rotates a spectrum so the zero-frequency bin sits at index `n/2`.
+pub fn fftshift<T>(x: &mut [T]) {
+    let half = x.len() / 2;
+    x.rotate_right(half);
+}
+
+/// Undoes [`fftshift`], also for odd lengths.
+pub fn ifftshift<T>(x: &mut [T]) {
+    let half = x.len() / 2;
+    x.rotate_left(half);
+}
+
+/// [`fftshift`] along both axes, moving the zero-frequency component to the center.
+pub fn fftshift2<T>(x: &mut Matrix2D<T>) {
+    fftshift(&mut x.data);
+    x.data.iter_mut().for_each(|row| fftshift(row));
+}
+
+/// Undoes [`fftshift2`].
+pub fn ifftshift2<T>(x: &mut Matrix2D<T>) {
+    ifftshift(&mut x.data);
+    x.data.iter_mut().for_each(|row| ifftshift(row));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fftshift_test() {
+        let mut even = vec![0, 1, 2, 3, -4, -3, -2, -1];
+        fftshift(&mut even);
+        assert_eq!(even, vec![-4, -3, -2, -1, 0, 1, 2, 3]);
+
+        let mut odd = vec![0, 1, 2, -2, -1];
+        fftshift(&mut odd);
+        assert_eq!(odd, vec![-2, -1, 0, 1, 2]);
+        ifftshift(&mut odd);
+        assert_eq!(odd, vec![0, 1, 2, -2, -1]);
+    }
+
+    #[test]
+    fn fftshift2_test() {
+        let mut mat = Matrix2D::new(vec![vec![0, 1, 2], vec![3, 4, 5]]).unwrap();
+        fftshift2(&mut mat);
+        assert_eq!(
+            mat,
+            Matrix2D::new(vec![vec![5, 3, 4], vec![2, 0, 1]]).unwrap()
+        );
+        ifftshift2(&mut mat);
+        assert_eq!(
+            mat,
+            Matrix2D::new(vec![vec![0, 1, 2], vec![3, 4, 5]]).unwrap()
+        );
+    }
+}
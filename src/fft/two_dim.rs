@@ -0,0 +1,124 @@
+use super::{cached_plan, zero, Direction, FftNum};
+use crate::complex::Complex;
+use crate::matrix::Matrix2D;
+
+/// Two-dimensional transform: every row is transformed, then every column.
+pub fn fft2<T: FftNum>(mut x: Matrix2D<Complex<T>>) -> Matrix2D<Complex<T>> {
+    transform2(&mut x, Direction::Forward);
+    x
+}
+
+/// Inverse of [`fft2`], scaled by `1/(rows * columns)`.
+pub fn ifft2<T: FftNum>(mut x: Matrix2D<Complex<T>>) -> Matrix2D<Complex<T>> {
+    transform2(&mut x, Direction::Inverse);
+
+    let n = T::from_usize(x.width() * x.height()).unwrap();
+    x.data.iter_mut().flatten().for_each(|c| {
+        c.re = c.re / n;
+        c.im = c.im / n;
+    });
+    x
+}
+
+fn transform2<T: FftNum>(x: &mut Matrix2D<Complex<T>>, direction: Direction) {
+    let (height, width) = (x.height(), x.width());
+    let row_plan = cached_plan::<T>(width, direction);
+    let column_plan = cached_plan::<T>(height, direction);
+    let zero = zero();
+    let mut scratch = vec![zero; row_plan.scratch_len().max(column_plan.scratch_len())];
+
+    for row in x.data.iter_mut() {
+        row_plan.process_with_scratch(row, &mut scratch);
+    }
+
+    let mut column = vec![zero; height];
+    for c in 0..width {
+        for (r, slot) in column.iter_mut().enumerate() {
+            *slot = x[r][c];
+        }
+        column_plan.process_with_scratch(&mut column, &mut scratch);
+        for (r, value) in column.iter().enumerate() {
+            x[r][c] = *value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+
+    fn grid(height: usize, width: usize) -> Matrix2D<Complex<f64>> {
+        Matrix2D::new(
+            (0..height)
+                .map(|r| {
+                    (0..width)
+                        .map(|c| Complex {
+                            re: ((r * 5 + c * 3) % 7) as f64 - 3.0,
+                            im: ((r * 2 + c) % 5) as f64 - 2.0,
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    /// Direct double sum over both axes.
+    fn dft2(x: &Matrix2D<Complex<f64>>) -> Matrix2D<Complex<f64>> {
+        let (h, w) = (x.height(), x.width());
+        Matrix2D::new(
+            (0..h)
+                .map(|u| {
+                    (0..w)
+                        .map(|v| {
+                            let mut acc = Complex { re: 0.0, im: 0.0 };
+                            for r in 0..h {
+                                for c in 0..w {
+                                    let angle = -std::f64::consts::TAU
+                                        * ((u * r) as f64 / h as f64 + (v * c) as f64 / w as f64);
+                                    let w = Complex {
+                                        re: angle.cos(),
+                                        im: angle.sin(),
+                                    };
+                                    acc = acc.add(&x[r][c].multiply(&w));
+                                }
+                            }
+                            acc
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn assert_close(a: &Matrix2D<Complex<f64>>, b: &Matrix2D<Complex<f64>>) {
+        assert_eq!((a.height(), a.width()), (b.height(), b.width()));
+        for (x, y) in a.data.iter().flatten().zip(b.data.iter().flatten()) {
+            assert!((x.re - y.re).abs() < 1e-9 && (x.im - y.im).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn fft2_matches_dft2_test() {
+        for (h, w) in [(1, 1), (4, 8), (3, 5), (6, 1), (7, 12)] {
+            let x = grid(h, w);
+            assert_close(&fft2(x.clone()), &dft2(&x));
+        }
+    }
+
+    #[test]
+    fn fft2_single_row_test() {
+        let x = grid(1, 10);
+        let res = fft2(x.clone());
+        let expected = Matrix2D::new(vec![fft(x[0].clone())]).unwrap();
+        assert_close(&res, &expected);
+    }
+
+    #[test]
+    fn ifft2_round_trip_test() {
+        let x = grid(9, 16);
+        assert_close(&ifft2(fft2(x.clone())), &x);
+    }
+}
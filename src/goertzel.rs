@@ -0,0 +1,268 @@
+use crate::complex::Complex;
+use crate::fft::FftNum;
+use std::f64::consts::TAU;
+
+/// Evaluates a single DFT bin with the Goertzel recurrence, one sample at a time.
+///
+/// The frequency need not be an integer bin: after `N` samples [`Goertzel::value`] returns
+/// `Σ x[n]·e^(-iωn)` for the configured angular frequency `ω`.
+#[derive(Debug, Clone)]
+pub struct Goertzel<T> {
+    omega: f64,
+    coeff: T,
+    s1: T,
+    s2: T,
+    count: usize,
+}
+
+impl<T: FftNum> Goertzel<T> {
+    /// Bin `bin` of a `length`-point DFT, i.e. `ω = 2π·bin/length`.
+    ///
+    /// # Panics
+    ///
+    /// If `length` is zero.
+    pub fn new(bin: f64, length: usize) -> Self {
+        assert!(length > 0, "DFT length must be positive");
+        Self::with_omega(TAU * bin / length as f64)
+    }
+
+    /// A tone of `frequency` in a signal sampled at `sample_rate`, in the same unit.
+    ///
+    /// # Panics
+    ///
+    /// If `sample_rate` is not positive.
+    pub fn from_frequency(frequency: f64, sample_rate: f64) -> Self {
+        assert!(sample_rate > 0.0, "sample rate must be positive");
+        Self::with_omega(TAU * frequency / sample_rate)
+    }
+
+    fn with_omega(omega: f64) -> Self {
+        Goertzel {
+            omega,
+            coeff: T::from_f64(2.0 * omega.cos()).unwrap(),
+            s1: T::zero(),
+            s2: T::zero(),
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, sample: T) {
+        let s0 = sample + self.coeff * self.s1 - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s0;
+        self.count += 1;
+    }
+
+    pub fn extend(&mut self, samples: &[T]) {
+        samples.iter().for_each(|&s| self.push(s));
+    }
+
+    /// Number of samples pushed since construction or the last reset.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The DFT value of the samples pushed so far, with the phase referred to the first one.
+    pub fn value(&self) -> Complex<T> {
+        if self.count == 0 {
+            return Complex {
+                re: T::zero(),
+                im: T::zero(),
+            };
+        }
+
+        // s1 - e^(-iω)·s2 equals e^(iω(N-1))·X(ω), so rotate back by ω(N-1).
+        let (sin, cos) = self.omega.sin_cos();
+        let raw = Complex {
+            re: self.s1 - T::from_f64(cos).unwrap() * self.s2,
+            im: T::from_f64(sin).unwrap() * self.s2,
+        };
+        let (sin, cos) = (-self.omega * (self.count - 1) as f64).sin_cos();
+        raw.multiply(&Complex {
+            re: T::from_f64(cos).unwrap(),
+            im: T::from_f64(sin).unwrap(),
+        })
+    }
+
+    /// `|X(ω)|²` without forming the complex value, which is all tone detection needs.
+    pub fn power(&self) -> T {
+        self.s1 * self.s1 + self.s2 * self.s2 - self.coeff * self.s1 * self.s2
+    }
+
+    pub fn reset(&mut self) {
+        self.s1 = T::zero();
+        self.s2 = T::zero();
+        self.count = 0;
+    }
+}
+
+/// Single bin of the DFT of a real signal; `bin` may be fractional. An empty signal gives zero.
+pub fn goertzel<T: FftNum>(x: &[T], bin: f64) -> Complex<T> {
+    if x.is_empty() {
+        return Complex {
+            re: T::zero(),
+            im: T::zero(),
+        };
+    }
+    let mut filter = Goertzel::new(bin, x.len());
+    filter.extend(x);
+    filter.value()
+}
+
+/// Selected bins `X(2π·k/N)` of the DFT of a real signal, at O(N) cost per bin.
+pub fn dft_bins<T: FftNum>(x: &[T], bins: &[f64]) -> Vec<Complex<T>> {
+    bins.iter().map(|&k| goertzel(x, k)).collect()
+}
+
+/// [`dft_bins`] for complex signals, running the recurrence on both parts.
+pub fn dft_bins_complex<T: FftNum>(x: &[Complex<T>], bins: &[f64]) -> Vec<Complex<T>> {
+    if x.is_empty() {
+        let zero = Complex {
+            re: T::zero(),
+            im: T::zero(),
+        };
+        return vec![zero; bins.len()];
+    }
+    bins.iter()
+        .map(|&k| {
+            let mut re = Goertzel::new(k, x.len());
+            let mut im = Goertzel::new(k, x.len());
+            for c in x {
+                re.push(c.re);
+                im.push(c.im);
+            }
+            let (a, b) = (re.value(), im.value());
+            // a + i·b
+            Complex {
+                re: a.re - b.im,
+                im: a.im + b.re,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+    use rust_decimal::Decimal;
+
+    fn signal(length: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| ((i * 7 + 3) % 11) as f64 - 5.0 + 0.25 * (i % 3) as f64)
+            .collect()
+    }
+
+    fn direct(x: &[f64], bin: f64) -> Complex<f64> {
+        let n = x.len() as f64;
+        x.iter()
+            .enumerate()
+            .fold(Complex { re: 0.0, im: 0.0 }, |acc, (i, &v)| {
+                let angle = -TAU * bin * i as f64 / n;
+                acc.add(&Complex {
+                    re: v * angle.cos(),
+                    im: v * angle.sin(),
+                })
+            })
+    }
+
+    fn assert_close(a: &Complex<f64>, b: &Complex<f64>) {
+        assert!(
+            (a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn integer_bins_match_fft_test() {
+        let x = signal(40);
+        let spectrum = fft(x.iter().map(|&re| Complex { re, im: 0.0 }).collect());
+        let bins: Vec<f64> = (0..40).map(|k| k as f64).collect();
+        for (a, b) in dft_bins(&x, &bins).iter().zip(spectrum.iter()) {
+            assert_close(a, b);
+        }
+    }
+
+    #[test]
+    fn fractional_bins_test() {
+        let x = signal(33);
+        for bin in [0.5, 2.25, 7.9, 16.5] {
+            assert_close(&goertzel(&x, bin), &direct(&x, bin));
+        }
+    }
+
+    #[test]
+    fn complex_bins_test() {
+        let x: Vec<Complex<f64>> = signal(24)
+            .into_iter()
+            .zip(signal(27).into_iter().skip(3))
+            .map(|(re, im)| Complex { re, im })
+            .collect();
+        let spectrum = fft(x.clone());
+        let res = dft_bins_complex(&x, &[1.0, 5.0, 23.0]);
+        for (a, k) in res.iter().zip([1, 5, 23]) {
+            assert_close(a, &spectrum[k]);
+        }
+    }
+
+    #[test]
+    fn power_test() {
+        let x = signal(50);
+        let mut filter = Goertzel::new(3.0, 50);
+        filter.extend(&x);
+        let value = filter.value();
+        assert!((filter.power() - (value.re * value.re + value.im * value.im)).abs() < 1e-8);
+        assert_eq!(filter.len(), 50);
+        filter.reset();
+        assert!(filter.is_empty());
+        assert_eq!(filter.value(), Complex { re: 0.0, im: 0.0 });
+    }
+
+    #[test]
+    fn dtmf_test() {
+        // Key "1": 697 Hz + 1209 Hz.
+        let rate = 8000.0;
+        let x: Vec<f64> = (0..205)
+            .map(|n| {
+                let t = n as f64 / rate;
+                (TAU * 697.0 * t).sin() + (TAU * 1209.0 * t).sin()
+            })
+            .collect();
+        let power = |f: f64| {
+            let mut filter = Goertzel::from_frequency(f, rate);
+            filter.extend(&x);
+            filter.power()
+        };
+        let rows = [697.0, 770.0, 852.0, 941.0].map(power);
+        let columns = [1209.0, 1336.0, 1477.0].map(power);
+        assert!(rows[1..].iter().all(|&p| p < rows[0] / 20.0));
+        assert!(columns[1..].iter().all(|&p| p < columns[0] / 20.0));
+    }
+
+    #[test]
+    fn empty_input_test() {
+        let zero = Complex {
+            re: Decimal::ZERO,
+            im: Decimal::ZERO,
+        };
+        assert_eq!(goertzel::<Decimal>(&[], 1.0), zero);
+        assert_eq!(dft_bins::<Decimal>(&[], &[0.0, 2.5]), vec![zero; 2]);
+        assert_eq!(dft_bins_complex::<Decimal>(&[], &[0.0, 2.5]), vec![zero; 2]);
+        assert_eq!(
+            dft_bins::<f64>(&[], &[1.0]),
+            vec![Complex { re: 0.0, im: 0.0 }]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DFT length must be positive")]
+    fn zero_length_test() {
+        Goertzel::<Decimal>::new(1.0, 0);
+    }
+}
@@ -1,5 +1,15 @@
-#![allow(dead_code)]
+pub mod complex;
+pub mod convolution;
+pub mod czt;
+pub mod dct;
+pub mod fft;
+pub mod goertzel;
+pub mod matrix;
+pub mod ntt;
+pub mod prelude;
+pub mod psd;
+pub mod stft;
+pub mod window;
 
-mod complex;
-mod matrix2d;
-mod fft;
+pub use complex::{Complex, ComplexError};
+pub use matrix::{Matrix2D, Matrix2DError};
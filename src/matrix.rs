@@ -35,23 +35,7 @@ impl<T> IndexMut<usize> for Matrix2D<T> {
     }
 }
 
-impl<T> Matrix2D<T>
-where
-    T: Add<Output = T>
-        + Sub<Output = T>
-        + Mul<Output = T>
-        + Div<Output = T>
-        + Clone
-        + Copy
-        + Zero
-        + Default
-        + FromPrimitive
-        + Sum
-        + Product
-        + PartialEq
-        + PartialOrd
-        + std::fmt::Debug,
-{
+impl<T> Matrix2D<T> {
     pub fn new(data: Vec<Vec<T>>) -> Result<Matrix2D<T>, Matrix2DError> {
         if data.is_empty() {
             return Err(Matrix2DError::EmptyMatrix);
@@ -70,20 +54,47 @@ where
         Ok(Matrix2D { width, data })
     }
 
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl<T> Matrix2D<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Clone
+        + Copy
+        + Zero
+        + Default
+        + FromPrimitive
+        + Sum
+        + Product
+        + PartialEq
+        + PartialOrd
+        + std::fmt::Debug,
+{
     pub fn diag(size: usize, value: T) -> Self {
         let mut data = vec![vec![T::zero(); size]; size];
 
-        for i in 0..size {
-            data[i][i] = value;
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = value;
         }
 
         Matrix2D::new(data).unwrap()
     }
 
     pub fn mul(&self, operand: &Matrix2D<T>) -> Result<Matrix2D<T>, Matrix2DError> {
-        match self.is_multiplicable(&operand) {
-            false => return Err(Matrix2DError::NotMultiplicable),
-            _ => {}
+        if !self.is_multiplicable(operand) {
+            return Err(Matrix2DError::NotMultiplicable);
         }
 
         let mut result: Vec<Vec<T>> = vec![Vec::new(); self.data.len()];
@@ -113,9 +124,8 @@ where
     }
 
     pub fn add(&self, operand: &Matrix2D<T>) -> Result<Matrix2D<T>, Matrix2DError> {
-        match self.is_additive(operand) {
-            false => return Err(Matrix2DError::NotAdditive),
-            _ => {}
+        if !self.is_additive(operand) {
+            return Err(Matrix2DError::NotAdditive);
         }
 
         Ok(Matrix2D::new(
@@ -129,9 +139,8 @@ where
     }
 
     pub fn substract(&self, operand: &Matrix2D<T>) -> Result<Matrix2D<T>, Matrix2DError> {
-        match self.is_additive(operand) {
-            false => return Err(Matrix2DError::NotAdditive),
-            _ => {}
+        if !self.is_additive(operand) {
+            return Err(Matrix2DError::NotAdditive);
         }
 
         Ok(Matrix2D::new(
@@ -145,9 +154,8 @@ where
     }
 
     pub fn det(&self) -> Result<T, Matrix2DError> {
-        match self.is_square() {
-            false => return Err(Matrix2DError::NotSquare),
-            _ => {}
+        if !self.is_square() {
+            return Err(Matrix2DError::NotSquare);
         }
 
         if self.width == 1 {
@@ -164,9 +172,8 @@ where
     }
 
     pub fn lu_decomposition(&self) -> Result<(Matrix2D<T>, Matrix2D<T>), Matrix2DError> {
-        match self.is_square() {
-            false => return Err(Matrix2DError::NotSquare),
-            _ => {}
+        if !self.is_square() {
+            return Err(Matrix2DError::NotSquare);
         }
 
         let mut l = Matrix2D::diag(self.width, T::from_i32(1).unwrap());
@@ -191,18 +198,48 @@ where
     }
 
     pub fn inverse(&self) -> Result<Matrix2D<T>, Matrix2DError> {
-        match self.is_square() {
-            false => return Err(Matrix2DError::NotSquare),
-            _ => {}
+        if !self.is_square() {
+            return Err(Matrix2DError::NotSquare);
         }
 
-        match self.det() {
-            Ok(det) if det == T::zero() => return Err(Matrix2DError::SingularMatrix),
-            Err(e) => return Err(e),
-            _ => {}
+        // Gauss-Jordan elimination on [A | I], taking the row with the largest pivot magnitude
+        // so that a zero on the diagonal does not stop a non-singular matrix.
+        let size = self.width;
+        let abs = |x: T| if x < T::zero() { T::zero() - x } else { x };
+        let mut a = self.clone();
+        let mut inverse = Matrix2D::diag(size, T::from_u8(1).unwrap());
+        for col in 0..size {
+            let pivot = (col..size)
+                .max_by(|&i, &j| {
+                    abs(a[i][col])
+                        .partial_cmp(&abs(a[j][col]))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            if a[pivot][col] == T::zero() {
+                return Err(Matrix2DError::SingularMatrix);
+            }
+            a.data.swap(col, pivot);
+            inverse.data.swap(col, pivot);
+
+            let scale = a[col][col];
+            for k in 0..size {
+                a[col][k] = a[col][k] / scale;
+                inverse[col][k] = inverse[col][k] / scale;
+            }
+            for row in (0..size).filter(|&row| row != col) {
+                let factor = a[row][col];
+                if factor == T::zero() {
+                    continue;
+                }
+                for k in 0..size {
+                    a[row][k] = a[row][k] - factor * a[col][k];
+                    inverse[row][k] = inverse[row][k] - factor * inverse[col][k];
+                }
+            }
         }
 
-        todo!()
+        Ok(inverse)
     }
 
     #[inline]
@@ -230,6 +267,12 @@ mod tests {
         assert!(Matrix2D::new(vec![vec![5, 15, 25], vec![8, 85, 25], vec![85, 25, 35]]).is_ok())
     }
 
+    #[test]
+    fn dimensions_test() {
+        let mat = Matrix2D::new(vec![vec!["a", "b", "c"], vec!["d", "e", "f"]]).unwrap();
+        assert_eq!((mat.height(), mat.width()), (2, 3));
+    }
+
     #[test]
     fn new_err_emptymatrix_test() {
         let mat: Result<Matrix2D<i32>, Matrix2DError> = Matrix2D::new(vec![]);
@@ -295,6 +338,7 @@ mod tests {
         );
     }
 
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
     #[test]
     fn det_test() {
@@ -325,6 +369,76 @@ mod tests {
         )
     }
 
+    #[test]
+    fn inverse_test() {
+        // Pivoting divides by values such as 7, so Decimal results carry rounding in the last
+        // digits.
+        let rounded = |m: Result<Matrix2D<Decimal>, Matrix2DError>| {
+            m.map(|m| {
+                Matrix2D::new(
+                    m.data
+                        .iter()
+                        .map(|row| row.iter().map(|x| x.round_dp(20).normalize()).collect())
+                        .collect(),
+                )
+                .unwrap()
+            })
+        };
+        assert_eq!(
+            rounded(
+                Matrix2D::new(vec![vec![dec!(5.0), dec!(7.0)], vec![dec!(7.0), dec!(9.0)]])
+                    .unwrap()
+                    .inverse()
+            ),
+            Matrix2D::new(vec![
+                vec![dec!(-2.25), dec!(1.75)],
+                vec![dec!(1.75), dec!(-1.25)]
+            ])
+        );
+        let m = Matrix2D::new(vec![
+            vec![dec!(2), dec!(1), dec!(1)],
+            vec![dec!(4), dec!(-6), dec!(0)],
+            vec![dec!(-2), dec!(7), dec!(2)],
+        ])
+        .unwrap();
+        assert_eq!(
+            rounded(m.inverse()),
+            Matrix2D::new(vec![
+                vec![dec!(0.75), dec!(-0.3125), dec!(-0.375)],
+                vec![dec!(0.5), dec!(-0.375), dec!(-0.25)],
+                vec![dec!(-1), dec!(1), dec!(1)],
+            ])
+        );
+        assert_eq!(
+            Matrix2D::new(vec![vec![2.0, 4.0], vec![1.0, 2.0]])
+                .unwrap()
+                .inverse(),
+            Err(Matrix2DError::SingularMatrix)
+        );
+    }
+
+    #[test]
+    fn inverse_zero_leading_pivot_test() {
+        let swap = vec![vec![dec!(0), dec!(1)], vec![dec!(1), dec!(0)]];
+        assert_eq!(
+            Matrix2D::new(swap.clone()).unwrap().inverse(),
+            Matrix2D::new(swap)
+        );
+        assert_eq!(
+            Matrix2D::new(vec![vec![0.0, 2.0], vec![4.0, 0.0]])
+                .unwrap()
+                .inverse(),
+            Matrix2D::new(vec![vec![0.0, 0.25], vec![0.5, 0.0]])
+        );
+        let m = Matrix2D::new(vec![
+            vec![dec!(0), dec!(2), dec!(1)],
+            vec![dec!(0), dec!(4), dec!(2)],
+            vec![dec!(3), dec!(1), dec!(5)],
+        ])
+        .unwrap();
+        assert_eq!(m.inverse(), Err(Matrix2DError::SingularMatrix));
+    }
+
     #[test]
     fn scalar_mult_test() {
         assert_eq!(
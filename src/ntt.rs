@@ -0,0 +1,490 @@
+/// A prime modulus `p = c·2^k + 1` together with a root of unity of order `2^k`, which
+/// allows number-theoretic transforms of any power-of-two length up to `2^k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NttPrime {
+    modulus: u64,
+    root: u64,
+    max_log2: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NttError {
+    NotPrime,
+    ModulusTooLarge,
+    /// The transform length is not a power of two or exceeds what the modulus supports.
+    UnsupportedLength,
+    /// The exact result may not fit below the product of the moduli (or in the output type).
+    CoefficientOverflow,
+    /// The same prime was passed twice, so the remainders cannot be combined.
+    DuplicateModulus,
+    /// No modulus was given to reconstruct the result from.
+    NoModuli,
+}
+
+/// Moduli below `2^62` keep `a + b` from overflowing before the reduction.
+const MAX_MODULUS: u64 = 1 << 62;
+
+/// Three NTT-friendly primes whose product (about `7.9·10^25`) bounds the coefficients
+/// [`multiply_exact`] can reconstruct.
+const DEFAULT_MODULI: [u64; 3] = [998_244_353, 167_772_161, 469_762_049];
+
+impl NttPrime {
+    /// Checks that `modulus` is prime and finds a root of unity of the largest power-of-two
+    /// order dividing `modulus - 1`.
+    pub fn new(modulus: u64) -> Result<NttPrime, NttError> {
+        if modulus >= MAX_MODULUS {
+            return Err(NttError::ModulusTooLarge);
+        }
+        if !is_prime(modulus) {
+            return Err(NttError::NotPrime);
+        }
+
+        let max_log2 = (modulus - 1).trailing_zeros();
+        let odd = (modulus - 1) >> max_log2;
+        // Any quadratic non-residue g gives g^odd a root of order exactly 2^max_log2.
+        let non_residue = (2..modulus)
+            .find(|&g| pow_mod(g, (modulus - 1) / 2, modulus) == modulus - 1)
+            .unwrap_or(1);
+
+        Ok(NttPrime {
+            modulus,
+            root: pow_mod(non_residue, odd, modulus),
+            max_log2,
+        })
+    }
+
+    /// The classic `119·2^23 + 1`.
+    pub fn p998244353() -> NttPrime {
+        NttPrime::new(998_244_353).unwrap()
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Longest transform the modulus supports.
+    pub fn max_len(&self) -> usize {
+        1usize << self.max_log2.min(usize::BITS - 1)
+    }
+
+    /// Root of unity of order exactly `length`, which must be a supported power of two.
+    fn root_of_order(&self, length: usize) -> u64 {
+        let steps = self.max_log2 - length.trailing_zeros();
+        pow_mod(self.root, 1 << steps, self.modulus)
+    }
+}
+
+/// Forward number-theoretic transform of `x` in place; every value is reduced modulo the
+/// prime first.
+pub fn ntt(x: &mut [u64], prime: &NttPrime) -> Result<(), NttError> {
+    transform(x, prime, false)
+}
+
+/// Inverse of [`ntt`], including the division by the length.
+pub fn intt(x: &mut [u64], prime: &NttPrime) -> Result<(), NttError> {
+    transform(x, prime, true)?;
+    if x.is_empty() {
+        return Ok(());
+    }
+
+    let p = prime.modulus;
+    let inverse_len = pow_mod(x.len() as u64 % p, p - 2, p);
+    x.iter_mut().for_each(|v| *v = mul_mod(*v, inverse_len, p));
+    Ok(())
+}
+
+/// Product of two polynomials with coefficients reduced modulo `prime`.
+pub fn multiply_mod(a: &[u64], b: &[u64], prime: &NttPrime) -> Result<Vec<u64>, NttError> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let length = a.len() + b.len() - 1;
+    let padded = length.next_power_of_two();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(padded, 0);
+    fb.resize(padded, 0);
+    ntt(&mut fa, prime)?;
+    ntt(&mut fb, prime)?;
+
+    let p = prime.modulus;
+    fa.iter_mut()
+        .zip(fb.iter())
+        .for_each(|(x, &y)| *x = mul_mod(*x, y, p));
+    intt(&mut fa, prime)?;
+    fa.truncate(length);
+    Ok(fa)
+}
+
+/// Exact product of two polynomials with non-negative coefficients, computed modulo every
+/// prime in `primes` and recombined with the Chinese remainder theorem.
+///
+/// Fails with [`NttError::CoefficientOverflow`] unless the worst-case coefficient,
+/// `min(a.len(), b.len()) · max(a) · max(b)`, is below the product of the moduli and that
+/// product fits in a `u128`.
+pub fn multiply_crt(a: &[u64], b: &[u64], primes: &[NttPrime]) -> Result<Vec<u128>, NttError> {
+    if primes.is_empty() {
+        return Err(NttError::NoModuli);
+    }
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let product = moduli_product(primes)?;
+    let bound = coefficient_bound(a, b).ok_or(NttError::CoefficientOverflow)?;
+    if bound >= product {
+        return Err(NttError::CoefficientOverflow);
+    }
+
+    let residues = primes
+        .iter()
+        .map(|prime| {
+            let reduce = |x: &[u64]| x.iter().map(|v| v % prime.modulus).collect::<Vec<_>>();
+            multiply_mod(&reduce(a), &reduce(b), prime)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((0..residues[0].len())
+        .map(|i| {
+            let r: Vec<u64> = residues.iter().map(|res| res[i]).collect();
+            crt(&r, primes)
+        })
+        .collect())
+}
+
+/// Exact product of two polynomials with signed coefficients, using three built-in
+/// NTT-friendly primes.
+pub fn multiply_exact(a: &[i64], b: &[i64]) -> Result<Vec<i128>, NttError> {
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let primes: Vec<NttPrime> = DEFAULT_MODULI
+        .iter()
+        .map(|&m| NttPrime::new(m).unwrap())
+        .collect();
+    let product = moduli_product(&primes)?;
+
+    let magnitude = |x: &[i64]| x.iter().map(|v| v.unsigned_abs()).collect::<Vec<_>>();
+    let bound =
+        coefficient_bound(&magnitude(a), &magnitude(b)).ok_or(NttError::CoefficientOverflow)?;
+    // Signed results are recovered from the symmetric range (-M/2, M/2).
+    if bound >= product / 2 {
+        return Err(NttError::CoefficientOverflow);
+    }
+
+    let residues = primes
+        .iter()
+        .map(|prime| {
+            let m = prime.modulus as i64;
+            let reduce = |x: &[i64]| x.iter().map(|v| v.rem_euclid(m) as u64).collect::<Vec<_>>();
+            multiply_mod(&reduce(a), &reduce(b), prime)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((0..residues[0].len())
+        .map(|i| {
+            let r: Vec<u64> = residues.iter().map(|res| res[i]).collect();
+            let value = crt(&r, &primes);
+            match value > product / 2 {
+                true => -((product - value) as i128),
+                false => value as i128,
+            }
+        })
+        .collect())
+}
+
+fn transform(x: &mut [u64], prime: &NttPrime, inverse: bool) -> Result<(), NttError> {
+    let length = x.len();
+    if length <= 1 {
+        x.iter_mut().for_each(|v| *v %= prime.modulus);
+        return Ok(());
+    }
+    if !length.is_power_of_two() || length > prime.max_len() {
+        return Err(NttError::UnsupportedLength);
+    }
+
+    let p = prime.modulus;
+    x.iter_mut().for_each(|v| *v %= p);
+
+    let bits = length.trailing_zeros();
+    for i in 0..length {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= length {
+        let mut w_step = prime.root_of_order(size);
+        if inverse {
+            w_step = pow_mod(w_step, p - 2, p);
+        }
+        let half = size / 2;
+        for start in (0..length).step_by(size) {
+            let mut w = 1;
+            for k in 0..half {
+                let u = x[start + k];
+                let t = mul_mod(x[start + k + half], w, p);
+                x[start + k] = add_mod(u, t, p);
+                x[start + k + half] = add_mod(u, p - t, p);
+                w = mul_mod(w, w_step, p);
+            }
+        }
+        size *= 2;
+    }
+
+    Ok(())
+}
+
+/// Garner's algorithm: builds the value from mixed-radix digits so that no intermediate
+/// exceeds the product of the moduli.
+fn crt(residues: &[u64], primes: &[NttPrime]) -> u128 {
+    let mut digits: Vec<u64> = Vec::with_capacity(residues.len());
+    for (i, (&r, prime)) in residues.iter().zip(primes.iter()).enumerate() {
+        let p = prime.modulus;
+        // Value of the digits so far modulo p, and the product of the earlier moduli mod p.
+        let (mut value, mut radix) = (0, 1);
+        for (d, earlier) in digits.iter().zip(primes[..i].iter()) {
+            value = add_mod(value, mul_mod(d % p, radix, p), p);
+            radix = mul_mod(radix, earlier.modulus % p, p);
+        }
+        let digit = mul_mod(add_mod(r % p, p - value, p), pow_mod(radix, p - 2, p), p);
+        digits.push(digit);
+    }
+
+    let mut result = 0u128;
+    let mut radix = 1u128;
+    for (d, prime) in digits.iter().zip(primes.iter()) {
+        result += *d as u128 * radix;
+        radix = radix.saturating_mul(prime.modulus as u128);
+    }
+    result
+}
+
+fn moduli_product(primes: &[NttPrime]) -> Result<u128, NttError> {
+    let mut product = 1u128;
+    for (i, prime) in primes.iter().enumerate() {
+        if primes[..i]
+            .iter()
+            .any(|other| other.modulus == prime.modulus)
+        {
+            return Err(NttError::DuplicateModulus);
+        }
+        product = product
+            .checked_mul(prime.modulus as u128)
+            .ok_or(NttError::CoefficientOverflow)?;
+    }
+    Ok(product)
+}
+
+/// Largest possible magnitude of a product coefficient, `None` if it overflows `u128`.
+fn coefficient_bound(a: &[u64], b: &[u64]) -> Option<u128> {
+    let max_a = *a.iter().max()? as u128;
+    let max_b = *b.iter().max()? as u128;
+    (a.len().min(b.len()) as u128)
+        .checked_mul(max_a)?
+        .checked_mul(max_b)
+}
+
+#[inline]
+fn add_mod(a: u64, b: u64, p: u64) -> u64 {
+    let sum = a + b;
+    if sum >= p {
+        sum - p
+    } else {
+        sum
+    }
+}
+
+#[inline]
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (a as u128 * b as u128 % p as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut result = 1 % p;
+    base %= p;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller-Rabin; these bases are sufficient for every 64-bit integer.
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = mul_mod(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_mod(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+        let mut out = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                out[i + j] = add_mod(out[i + j], mul_mod(x % p, y % p, p), p);
+            }
+        }
+        out
+    }
+
+    fn naive_signed(a: &[i64], b: &[i64]) -> Vec<i128> {
+        let mut out = vec![0i128; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                out[i + j] += x as i128 * y as i128;
+            }
+        }
+        out
+    }
+
+    fn sequence(length: usize, scale: u64) -> Vec<u64> {
+        (0..length as u64)
+            .map(|i| (i * 2654435761 + 12345) % scale)
+            .collect()
+    }
+
+    #[test]
+    fn prime_setup_test() {
+        let p = NttPrime::p998244353();
+        assert_eq!(p.modulus(), 998_244_353);
+        assert_eq!(p.max_len(), 1 << 23);
+        assert_eq!(pow_mod(p.root, 1 << 23, p.modulus), 1);
+        assert_ne!(pow_mod(p.root, 1 << 22, p.modulus), 1);
+
+        assert_eq!(NttPrime::new(7_340_033).unwrap().max_len(), 1 << 20);
+        assert_eq!(NttPrime::new(998_244_351), Err(NttError::NotPrime));
+        assert_eq!(NttPrime::new(1), Err(NttError::NotPrime));
+        assert_eq!(NttPrime::new(u64::MAX), Err(NttError::ModulusTooLarge));
+    }
+
+    #[test]
+    fn ntt_round_trip_test() {
+        let prime = NttPrime::p998244353();
+        for length in [1, 2, 4, 64, 1024] {
+            let x = sequence(length, prime.modulus());
+            let mut y = x.clone();
+            ntt(&mut y, &prime).unwrap();
+            intt(&mut y, &prime).unwrap();
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn ntt_matches_definition_test() {
+        let prime = NttPrime::new(17).unwrap();
+        let x = vec![1, 2, 3, 4];
+        let mut y = x.clone();
+        ntt(&mut y, &prime).unwrap();
+        let w = prime.root_of_order(4);
+        let expected: Vec<u64> = (0..4)
+            .map(|k| {
+                (0..4).fold(0, |acc, n| {
+                    add_mod(acc, mul_mod(x[n], pow_mod(w, (n * k) as u64, 17), 17), 17)
+                })
+            })
+            .collect();
+        assert_eq!(y, expected);
+    }
+
+    #[test]
+    fn unsupported_length_test() {
+        let prime = NttPrime::new(1_000_000_007).unwrap();
+        assert_eq!(prime.max_len(), 2);
+        assert_eq!(
+            ntt(&mut [1, 2, 3, 4], &prime),
+            Err(NttError::UnsupportedLength)
+        );
+        assert_eq!(
+            ntt(&mut [1, 2, 3], &NttPrime::p998244353()),
+            Err(NttError::UnsupportedLength)
+        );
+    }
+
+    #[test]
+    fn multiply_mod_test() {
+        for prime in [NttPrime::p998244353(), NttPrime::new(469_762_049).unwrap()] {
+            let (a, b) = (sequence(300, u64::MAX), sequence(77, u64::MAX));
+            assert_eq!(
+                multiply_mod(&a, &b, &prime).unwrap(),
+                naive_mod(&a, &b, prime.modulus())
+            );
+        }
+        assert_eq!(multiply_mod(&[], &[1], &NttPrime::p998244353()), Ok(vec![]));
+    }
+
+    #[test]
+    fn multiply_crt_test() {
+        let primes: Vec<NttPrime> = DEFAULT_MODULI
+            .iter()
+            .map(|&m| NttPrime::new(m).unwrap())
+            .collect();
+        let (a, b) = (sequence(500, 1 << 30), sequence(400, 1 << 30));
+        let res = multiply_crt(&a, &b, &primes).unwrap();
+        let signed = |x: &[u64]| x.iter().map(|&v| v as i64).collect::<Vec<_>>();
+        let expected = naive_signed(&signed(&a), &signed(&b));
+        assert!(res
+            .iter()
+            .zip(expected.iter())
+            .all(|(&x, &y)| x as i128 == y));
+        assert!(expected.iter().any(|&v| v > 998_244_353 * 469_762_049));
+
+        assert_eq!(
+            multiply_crt(&[u64::MAX; 4], &[u64::MAX; 4], &primes[..1]),
+            Err(NttError::CoefficientOverflow)
+        );
+        assert_eq!(
+            multiply_crt(&[1], &[1], &[primes[0], primes[0]]),
+            Err(NttError::DuplicateModulus)
+        );
+        assert_eq!(multiply_crt(&[0], &[0], &[]), Err(NttError::NoModuli));
+    }
+
+    #[test]
+    fn multiply_exact_test() {
+        let a: Vec<i64> = sequence(257, 2_000_000_000)
+            .iter()
+            .map(|&v| v as i64 - 1_000_000_000)
+            .collect();
+        let b: Vec<i64> = sequence(190, 4_000_000_000)
+            .iter()
+            .map(|&v| 2_000_000_000 - v as i64)
+            .collect();
+        assert_eq!(multiply_exact(&a, &b).unwrap(), naive_signed(&a, &b));
+        assert_eq!(
+            multiply_exact(&[-3, 2], &[5, -1]).unwrap(),
+            vec![-15, 13, -2]
+        );
+        assert_eq!(
+            multiply_exact(&[i64::MIN; 8], &[i64::MAX; 8]),
+            Err(NttError::CoefficientOverflow)
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub use crate::complex::{Complex, ComplexError};
+pub use crate::fft::{fft, fft_in_place, ifft, ifft_in_place, irfft, rfft, Normalization};
+pub use crate::matrix::{Matrix2D, Matrix2DError};
@@ -0,0 +1,426 @@
+use crate::fft::{fftfreq, rfft, rfftfreq, FftNum};
+
+/// Trend removed from every segment before it is windowed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Detrend {
+    None,
+    /// Subtract the segment mean.
+    #[default]
+    Constant,
+    /// Subtract the least-squares straight line through the segment.
+    Linear,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sides {
+    /// Bins `0..=n/2` with the power of the negative frequencies folded in, as for a real
+    /// signal.
+    #[default]
+    OneSided,
+    /// All `n` bins in [`fftfreq`] order.
+    TwoSided,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Scaling {
+    /// Power spectral density in units²/Hz: integrating over frequency gives the variance.
+    #[default]
+    Density,
+    /// Power spectrum in units²: a sinusoid of amplitude `A` centred on a bin reads `A²/2`.
+    Spectrum,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PsdError {
+    EmptyWindow,
+    SignalTooShort,
+    /// The periodogram window must cover the whole signal.
+    WindowLengthMismatch,
+    /// Segments must advance by at least one sample.
+    OverlapTooLarge,
+    /// The window normalisation is zero: `Σw²` for [`Scaling::Density`], which needs a
+    /// non-zero window, or `(Σw)²` for [`Scaling::Spectrum`], which needs a non-zero sum.
+    ZeroWindowNorm,
+    InvalidSampleRate,
+}
+
+/// A power spectral estimate and the frequency of each of its bins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Psd<T> {
+    pub frequencies: Vec<f64>,
+    pub power: Vec<T>,
+}
+
+/// Periodogram of a real signal: the scaled `|X|²` of the whole detrended, windowed signal.
+/// `window` must be as long as `x`.
+pub fn periodogram<T: FftNum>(
+    x: &[T],
+    sample_rate: f64,
+    window: &[T],
+    detrend: Detrend,
+    sides: Sides,
+    scaling: Scaling,
+) -> Result<Psd<T>, PsdError> {
+    if window.len() != x.len() {
+        return Err(PsdError::WindowLengthMismatch);
+    }
+    welch(x, sample_rate, window, 0, detrend, sides, scaling)
+}
+
+/// Welch's method: the average of the periodograms of segments of `window.len()` samples,
+/// consecutive segments sharing `overlap` samples. Samples after the last full segment are
+/// ignored.
+pub fn welch<T: FftNum>(
+    x: &[T],
+    sample_rate: f64,
+    window: &[T],
+    overlap: usize,
+    detrend: Detrend,
+    sides: Sides,
+    scaling: Scaling,
+) -> Result<Psd<T>, PsdError> {
+    let segment_len = window.len();
+    if segment_len == 0 {
+        return Err(PsdError::EmptyWindow);
+    }
+    if overlap >= segment_len {
+        return Err(PsdError::OverlapTooLarge);
+    }
+    if !(sample_rate.is_finite() && sample_rate > 0.0) {
+        return Err(PsdError::InvalidSampleRate);
+    }
+    if x.len() < segment_len {
+        return Err(PsdError::SignalTooShort);
+    }
+
+    let norm = match scaling {
+        Scaling::Density => {
+            let energy = window.iter().fold(T::zero(), |acc, &w| acc + w * w);
+            energy * T::from_f64(sample_rate).unwrap()
+        }
+        Scaling::Spectrum => {
+            let sum = window.iter().fold(T::zero(), |acc, &w| acc + w);
+            sum * sum
+        }
+    };
+    if norm.is_zero() {
+        return Err(PsdError::ZeroWindowNorm);
+    }
+
+    let step = segment_len - overlap;
+    let segments = 1 + (x.len() - segment_len) / step;
+    let mut power = vec![T::zero(); segment_len / 2 + 1];
+    for s in 0..segments {
+        let mut segment = x[s * step..s * step + segment_len].to_vec();
+        remove_trend(&mut segment, detrend);
+        segment
+            .iter_mut()
+            .zip(window.iter())
+            .for_each(|(v, &w)| *v = *v * w);
+        for (p, c) in power.iter_mut().zip(rfft(&segment).iter()) {
+            *p = *p + c.re * c.re + c.im * c.im;
+        }
+    }
+
+    let scale = norm * T::from_usize(segments).unwrap();
+    power.iter_mut().for_each(|p| *p = *p / scale);
+
+    Ok(match sides {
+        Sides::OneSided => {
+            // Every bin but DC and, for even lengths, Nyquist has a negative-frequency twin.
+            let paired = (segment_len - 1) / 2;
+            let two = T::one() + T::one();
+            power[1..=paired].iter_mut().for_each(|p| *p = *p * two);
+            Psd {
+                frequencies: rfftfreq(segment_len, sample_rate),
+                power,
+            }
+        }
+        Sides::TwoSided => {
+            let mut full = power.clone();
+            full.extend((segment_len / 2 + 1..segment_len).map(|k| power[segment_len - k]));
+            Psd {
+                frequencies: fftfreq(segment_len, sample_rate),
+                power: full,
+            }
+        }
+    })
+}
+
+fn remove_trend<T: FftNum>(x: &mut [T], detrend: Detrend) {
+    let n = T::from_usize(x.len()).unwrap();
+    match detrend {
+        Detrend::None => {}
+        Detrend::Constant => {
+            let mean = x.iter().fold(T::zero(), |acc, &v| acc + v) / n;
+            x.iter_mut().for_each(|v| *v = *v - mean);
+        }
+        Detrend::Linear => {
+            if x.len() < 2 {
+                return remove_trend(x, Detrend::Constant);
+            }
+            // Least-squares fit against t centred on zero, which decouples slope and offset.
+            let two = T::one() + T::one();
+            let center = (n - T::one()) / two;
+            let t = |i: usize| T::from_usize(i).unwrap() - center;
+            let mean = x.iter().fold(T::zero(), |acc, &v| acc + v) / n;
+            let (num, den) = x
+                .iter()
+                .enumerate()
+                .fold((T::zero(), T::zero()), |(num, den), (i, &v)| {
+                    (num + t(i) * v, den + t(i) * t(i))
+                });
+            let slope = num / den;
+            x.iter_mut()
+                .enumerate()
+                .for_each(|(i, v)| *v = *v - mean - slope * t(i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::{self, Symmetry};
+    use num::ToPrimitive;
+    use rust_decimal::Decimal;
+    use std::f64::consts::TAU;
+
+    fn random_signal(length: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..length)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect()
+    }
+
+    fn tone(length: usize, frequency: f64, amplitude: f64, sample_rate: f64) -> Vec<f64> {
+        (0..length)
+            .map(|i| amplitude * (TAU * frequency * i as f64 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn parseval_test() {
+        // A rectangular window without detrending integrates to the mean square exactly.
+        let x = random_signal(100, 3);
+        let fs = 50.0;
+        for sides in [Sides::OneSided, Sides::TwoSided] {
+            let psd =
+                periodogram(&x, fs, &[1.0; 100], Detrend::None, sides, Scaling::Density).unwrap();
+            let df = fs / 100.0;
+            let total: f64 = psd.power.iter().sum::<f64>() * df;
+            let mean_square = x.iter().map(|v| v * v).sum::<f64>() / 100.0;
+            assert!((total - mean_square).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn layout_test() {
+        let x = random_signal(64, 1);
+        let w: Vec<f64> = window::hann(16, Symmetry::Periodic);
+        let one = welch(
+            &x,
+            8.0,
+            &w,
+            8,
+            Detrend::Constant,
+            Sides::OneSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        assert_eq!(one.frequencies, rfftfreq(16, 8.0));
+        assert_eq!(one.power.len(), 9);
+        let two = welch(
+            &x,
+            8.0,
+            &w,
+            8,
+            Detrend::Constant,
+            Sides::TwoSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        assert_eq!(two.frequencies, fftfreq(16, 8.0));
+        assert_eq!(two.power.len(), 16);
+        assert!((one.power[3] - 2.0 * two.power[3]).abs() < 1e-15);
+        assert_eq!(two.power[3], two.power[13]);
+        assert_eq!(one.power[8], two.power[8]);
+        let sum = |p: &[f64]| p.iter().sum::<f64>();
+        assert!((sum(&one.power) - sum(&two.power)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn white_noise_level_test() {
+        // Uniform noise on [-0.5, 0.5) has variance 1/12, spread evenly over fs/2.
+        let fs = 1000.0;
+        let x = random_signal(1 << 16, 7);
+        let w: Vec<f64> = window::hann(256, Symmetry::Periodic);
+        let psd = welch(
+            &x,
+            fs,
+            &w,
+            128,
+            Detrend::Constant,
+            Sides::OneSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        let expected = 2.0 / 12.0 / fs;
+        let interior = &psd.power[1..128];
+        let mean = interior.iter().sum::<f64>() / interior.len() as f64;
+        assert!(
+            (mean - expected).abs() < 0.02 * expected,
+            "{} vs {}",
+            mean,
+            expected
+        );
+        assert!(interior
+            .iter()
+            .all(|&p| p > 0.5 * expected && p < 1.5 * expected));
+    }
+
+    #[test]
+    fn spectrum_scaling_test() {
+        let fs = 1024.0;
+        let x = tone(4096, 64.0, 3.0, fs);
+        let w: Vec<f64> = window::hann(512, Symmetry::Periodic);
+        let psd = welch(
+            &x,
+            fs,
+            &w,
+            256,
+            Detrend::Constant,
+            Sides::OneSided,
+            Scaling::Spectrum,
+        )
+        .unwrap();
+        let bin = psd.frequencies.iter().position(|&f| f == 64.0).unwrap();
+        assert!((psd.power[bin] - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn detrend_test() {
+        let fs = 1.0;
+        let w = [1.0; 32];
+        let offset: Vec<f64> = (0..32).map(|i| 5.0 + (i % 4) as f64).collect();
+        let psd = periodogram(
+            &offset,
+            fs,
+            &w,
+            Detrend::Constant,
+            Sides::OneSided,
+            Scaling::Spectrum,
+        )
+        .unwrap();
+        assert!(psd.power[0].abs() < 1e-20);
+        let psd = periodogram(
+            &offset,
+            fs,
+            &w,
+            Detrend::None,
+            Sides::OneSided,
+            Scaling::Spectrum,
+        )
+        .unwrap();
+        assert!((psd.power[0] - 6.5 * 6.5).abs() < 1e-9);
+
+        let ramp: Vec<f64> = (0..32).map(|i| 2.0 - 0.3 * i as f64).collect();
+        let psd = periodogram(
+            &ramp,
+            fs,
+            &w,
+            Detrend::Linear,
+            Sides::TwoSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        assert!(psd.power.iter().all(|p| p.abs() < 1e-20));
+    }
+
+    #[test]
+    fn decimal_test() {
+        let x: Vec<Decimal> = random_signal(32, 5)
+            .iter()
+            .map(|&v| Decimal::from_f64_retain(v).unwrap().round_dp(8))
+            .collect();
+        let xf: Vec<f64> = x.iter().map(|v| v.to_f64().unwrap()).collect();
+        let w: Vec<Decimal> = window::hann(8, Symmetry::Periodic);
+        let wf: Vec<f64> = window::hann(8, Symmetry::Periodic);
+        let a = welch(
+            &x,
+            2.0,
+            &w,
+            4,
+            Detrend::Linear,
+            Sides::OneSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        let b = welch(
+            &xf,
+            2.0,
+            &wf,
+            4,
+            Detrend::Linear,
+            Sides::OneSided,
+            Scaling::Density,
+        )
+        .unwrap();
+        for (p, q) in a.power.iter().zip(b.power.iter()) {
+            assert!((p.to_f64().unwrap() - q).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn error_test() {
+        let x = [1.0; 10];
+        let w = [1.0; 4];
+        let run = |x: &[f64], w: &[f64], overlap, fs| {
+            welch(
+                x,
+                fs,
+                w,
+                overlap,
+                Detrend::None,
+                Sides::OneSided,
+                Scaling::Density,
+            )
+        };
+        assert_eq!(run(&x, &[], 0, 1.0), Err(PsdError::EmptyWindow));
+        assert_eq!(run(&x, &w, 4, 1.0), Err(PsdError::OverlapTooLarge));
+        assert_eq!(run(&x, &w, 0, 0.0), Err(PsdError::InvalidSampleRate));
+        assert_eq!(run(&x[..3], &w, 0, 1.0), Err(PsdError::SignalTooShort));
+        assert_eq!(
+            periodogram(
+                &x,
+                1.0,
+                &w,
+                Detrend::None,
+                Sides::OneSided,
+                Scaling::Density
+            ),
+            Err(PsdError::WindowLengthMismatch)
+        );
+        assert_eq!(run(&x, &[0.0; 4], 0, 1.0), Err(PsdError::ZeroWindowNorm));
+        let zero_sum = [Decimal::ONE, -Decimal::ONE, Decimal::ONE, -Decimal::ONE];
+        let y = [Decimal::ONE; 10];
+        let spectrum = |scaling| {
+            welch(
+                &y,
+                1.0,
+                &zero_sum,
+                0,
+                Detrend::None,
+                Sides::OneSided,
+                scaling,
+            )
+        };
+        assert_eq!(spectrum(Scaling::Spectrum), Err(PsdError::ZeroWindowNorm));
+        assert!(spectrum(Scaling::Density).is_ok());
+    }
+}
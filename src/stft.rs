@@ -0,0 +1,321 @@
+use crate::complex::Complex;
+use crate::fft::{irfft, rfft, FftNum};
+use crate::matrix::Matrix2D;
+
+/// How the signal is extended so that frames can be centred on its first and last samples.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// No padding: the first frame starts at sample zero.
+    None,
+    /// `frame_len / 2` zeros on both ends; frame `t` is centred on sample `t * hop`.
+    #[default]
+    Zero,
+    /// Like [`Padding::Zero`] but mirrors the signal around its end samples.
+    Reflect,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StftError {
+    EmptyWindow,
+    ZeroHop,
+    SignalTooShort,
+    BinCountMismatch,
+    /// Some output sample is covered by no non-zero window value, so it cannot be recovered.
+    /// Squared-window sums below the element type's precision relative to the largest sum
+    /// count as zero.
+    WindowOverlapTooSmall,
+    /// The requested output length runs past the last frame.
+    LengthExceedsFrames,
+}
+
+/// Short-time Fourier transform of a real signal. The frame length is `window.len()` and
+/// row `t` of the result holds the `frame_len / 2 + 1` bins of the windowed frame
+/// starting `t * hop` samples into the (padded) signal.
+pub fn stft<T: FftNum>(
+    x: &[T],
+    window: &[T],
+    hop: usize,
+    padding: Padding,
+) -> Result<Matrix2D<Complex<T>>, StftError> {
+    let frame_len = window.len();
+    check_params(frame_len, hop)?;
+
+    let padded = pad(x, frame_len / 2, padding)?;
+    if padded.len() < frame_len {
+        return Err(StftError::SignalTooShort);
+    }
+
+    let frames = 1 + (padded.len() - frame_len) / hop;
+    let rows = (0..frames)
+        .map(|t| {
+            let frame: Vec<T> = padded[t * hop..t * hop + frame_len]
+                .iter()
+                .zip(window.iter())
+                .map(|(&v, &w)| v * w)
+                .collect();
+            rfft(&frame)
+        })
+        .collect();
+
+    Ok(Matrix2D::new(rows).unwrap())
+}
+
+/// Inverse of [`stft`] by weighted overlap-add, dividing by the summed squared window.
+/// `window`, `hop` and `padding` must be those used for the forward transform; `length`
+/// trims the result to the original signal length and may not exceed what the frames cover.
+pub fn istft<T: FftNum>(
+    spectrogram: &Matrix2D<Complex<T>>,
+    window: &[T],
+    hop: usize,
+    padding: Padding,
+    length: Option<usize>,
+) -> Result<Vec<T>, StftError> {
+    let frame_len = window.len();
+    check_params(frame_len, hop)?;
+    if spectrogram.width() != frame_len / 2 + 1 {
+        return Err(StftError::BinCountMismatch);
+    }
+
+    let total = frame_len + hop * (spectrogram.height() - 1);
+    let mut out = vec![T::zero(); total];
+    let mut norm = vec![T::zero(); total];
+    for (t, row) in spectrogram.data.iter().enumerate() {
+        let frame = irfft(row, frame_len);
+        for (i, (&v, &w)) in frame.iter().zip(window.iter()).enumerate() {
+            out[t * hop + i] = out[t * hop + i] + v * w;
+            norm[t * hop + i] = norm[t * hop + i] + w * w;
+        }
+    }
+
+    let (start, end) = match padding {
+        Padding::None => (0, total),
+        Padding::Zero | Padding::Reflect => (frame_len / 2, total - frame_len / 2),
+    };
+    let end = match length {
+        Some(length) if start + length > total => return Err(StftError::LengthExceedsFrames),
+        Some(length) => start + length,
+        None => end,
+    };
+
+    let peak = norm
+        .iter()
+        .map(|v| v.to_f64().unwrap().abs())
+        .fold(0.0, f64::max);
+    let mut signal = Vec::with_capacity(end - start);
+    for i in start..end {
+        if norm[i].to_f64().unwrap().abs() <= peak * T::EPSILON {
+            return Err(StftError::WindowOverlapTooSmall);
+        }
+        signal.push(out[i] / norm[i]);
+    }
+
+    Ok(signal)
+}
+
+/// `|X|` for every bin.
+pub fn magnitude_spectrogram<T: FftNum>(spectrogram: &Matrix2D<Complex<T>>) -> Matrix2D<T> {
+    map_bins(spectrogram, |c| {
+        T::from_f64(power(c).to_f64().unwrap().sqrt()).unwrap()
+    })
+}
+
+/// `|X|²` for every bin.
+pub fn power_spectrogram<T: FftNum>(spectrogram: &Matrix2D<Complex<T>>) -> Matrix2D<T> {
+    map_bins(spectrogram, power)
+}
+
+/// `10·log10(|X|²)` for every bin, clamped from below at `floor_db` so empty bins do not
+/// produce negative infinity.
+pub fn db_spectrogram<T: FftNum>(spectrogram: &Matrix2D<Complex<T>>, floor_db: f64) -> Matrix2D<T> {
+    map_bins(spectrogram, |c| {
+        let db = 10.0 * power(c).to_f64().unwrap().log10();
+        T::from_f64(db.max(floor_db)).unwrap()
+    })
+}
+
+fn map_bins<T: FftNum>(
+    spectrogram: &Matrix2D<Complex<T>>,
+    f: impl Fn(&Complex<T>) -> T,
+) -> Matrix2D<T> {
+    Matrix2D::new(
+        spectrogram
+            .data
+            .iter()
+            .map(|row| row.iter().map(&f).collect())
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[inline]
+fn power<T: FftNum>(c: &Complex<T>) -> T {
+    c.re * c.re + c.im * c.im
+}
+
+fn check_params(frame_len: usize, hop: usize) -> Result<(), StftError> {
+    if frame_len == 0 {
+        return Err(StftError::EmptyWindow);
+    }
+    if hop == 0 {
+        return Err(StftError::ZeroHop);
+    }
+    Ok(())
+}
+
+fn pad<T: FftNum>(x: &[T], amount: usize, padding: Padding) -> Result<Vec<T>, StftError> {
+    match padding {
+        Padding::None => Ok(x.to_vec()),
+        Padding::Zero => {
+            let mut padded = vec![T::zero(); amount];
+            padded.extend_from_slice(x);
+            padded.resize(x.len() + 2 * amount, T::zero());
+            Ok(padded)
+        }
+        Padding::Reflect => {
+            if x.len() <= amount {
+                return Err(StftError::SignalTooShort);
+            }
+            let mut padded: Vec<T> = x[1..=amount].iter().rev().copied().collect();
+            padded.extend_from_slice(x);
+            padded.extend(x[x.len() - 1 - amount..x.len() - 1].iter().rev());
+            Ok(padded)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::{self, Symmetry};
+
+    fn hann(length: usize) -> Vec<f64> {
+        window::hann(length, Symmetry::Periodic)
+    }
+
+    fn signal(length: usize) -> Vec<f64> {
+        (0..length)
+            .map(|i| (0.05 * i as f64).sin() + 0.25 * ((i * 7) % 5) as f64)
+            .collect()
+    }
+
+    #[test]
+    fn stft_shape_test() {
+        let x = signal(100);
+        let spec = stft(&x, &hann(16), 4, Padding::Zero).unwrap();
+        assert_eq!((spec.height(), spec.width()), (26, 9));
+        let spec = stft(&x, &hann(16), 4, Padding::None).unwrap();
+        assert_eq!((spec.height(), spec.width()), (22, 9));
+    }
+
+    #[test]
+    fn stft_frame_test() {
+        let x = signal(64);
+        let window = hann(16);
+        let spec = stft(&x, &window, 8, Padding::None).unwrap();
+        let frame: Vec<f64> = x[24..40]
+            .iter()
+            .zip(window.iter())
+            .map(|(a, b)| a * b)
+            .collect();
+        for (a, b) in spec[3].iter().zip(rfft(&frame).iter()) {
+            assert!((a.re - b.re).abs() < 1e-12 && (a.im - b.im).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let x = signal(203);
+        for (frame_len, hop) in [(16, 4), (32, 8), (15, 5), (64, 16)] {
+            for padding in [Padding::Zero, Padding::Reflect] {
+                let window = hann(frame_len);
+                let spec = stft(&x, &window, hop, padding).unwrap();
+                let y = istft(&spec, &window, hop, padding, Some(x.len())).unwrap();
+                assert_eq!(y.len(), x.len());
+                assert!(x.iter().zip(y.iter()).all(|(a, b)| (a - b).abs() < 1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn rectangular_round_trip_test() {
+        let x = signal(96);
+        let window = vec![1.0; 12];
+        let spec = stft(&x, &window, 12, Padding::None).unwrap();
+        let y = istft(&spec, &window, 12, Padding::None, None).unwrap();
+        assert!(x.iter().zip(y.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    fn reflect_padding_test() {
+        assert_eq!(
+            pad(&[1.0, 2.0, 3.0, 4.0], 2, Padding::Reflect).unwrap(),
+            vec![3.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 2.0]
+        );
+        assert_eq!(
+            pad(&[1.0, 2.0], 2, Padding::Reflect),
+            Err(StftError::SignalTooShort)
+        );
+    }
+
+    #[test]
+    fn errors_test() {
+        let x = signal(10);
+        assert_eq!(
+            stft(&x, &[], 1, Padding::Zero).err(),
+            Some(StftError::EmptyWindow)
+        );
+        assert_eq!(
+            stft(&x, &hann(4), 0, Padding::Zero).err(),
+            Some(StftError::ZeroHop)
+        );
+        assert_eq!(
+            stft(&x, &hann(16), 4, Padding::None).err(),
+            Some(StftError::SignalTooShort)
+        );
+
+        let spec = stft(&signal(64), &hann(16), 4, Padding::None).unwrap();
+        assert_eq!(
+            istft(&spec, &hann(8), 4, Padding::None, None).err(),
+            Some(StftError::BinCountMismatch)
+        );
+        // hann(16)[0] is zero, so the very first sample is not recoverable without padding.
+        assert_eq!(
+            istft(&spec, &hann(16), 4, Padding::None, None).err(),
+            Some(StftError::WindowOverlapTooSmall)
+        );
+        // 17 frames of 16 with hop 4 span 80 padded samples, 72 after the 8 leading ones.
+        let spec = stft(&signal(64), &hann(16), 4, Padding::Zero).unwrap();
+        assert_eq!(
+            istft(&spec, &hann(16), 4, Padding::Zero, Some(72))
+                .unwrap()
+                .len(),
+            72
+        );
+        assert_eq!(
+            istft(&spec, &hann(16), 4, Padding::Zero, Some(73)).err(),
+            Some(StftError::LengthExceedsFrames)
+        );
+    }
+
+    #[test]
+    fn spectrogram_helpers_test() {
+        let spec = Matrix2D::new(vec![
+            vec![Complex { re: 3.0, im: 4.0 }, Complex { re: 0.0, im: 0.0 }],
+            vec![Complex { re: 10.0, im: 0.0 }, Complex { re: 0.0, im: -1.0 }],
+        ])
+        .unwrap();
+        assert_eq!(
+            magnitude_spectrogram(&spec),
+            Matrix2D::new(vec![vec![5.0, 0.0], vec![10.0, 1.0]]).unwrap()
+        );
+        assert_eq!(
+            power_spectrogram(&spec),
+            Matrix2D::new(vec![vec![25.0, 0.0], vec![100.0, 1.0]]).unwrap()
+        );
+        let db: Matrix2D<f64> = db_spectrogram(&spec, -100.0);
+        assert!((db[0][0] - 13.979400086720377).abs() < 1e-12);
+        assert_eq!(db[0][1], -100.0);
+        assert!((db[1][0] - 20.0).abs() < 1e-12);
+        assert!(db[1][1].abs() < 1e-12);
+    }
+}
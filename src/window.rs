@@ -0,0 +1,294 @@
+use crate::complex::Complex;
+use num::{FromPrimitive, Num};
+use std::f64::consts::TAU;
+
+/// Whether a window is built for filter design or for spectral analysis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// `w[n] == w[len - 1 - n]`; the usual choice for FIR filter design.
+    Symmetric,
+    /// One period of a periodic window, i.e. the symmetric window of `len + 1` samples
+    /// without its last one. Preferred before an FFT.
+    #[default]
+    Periodic,
+}
+
+pub fn hann<T: Num + FromPrimitive>(length: usize, symmetry: Symmetry) -> Vec<T> {
+    cosine_sum(length, symmetry, &[0.5, 0.5])
+}
+
+pub fn hamming<T: Num + FromPrimitive>(length: usize, symmetry: Symmetry) -> Vec<T> {
+    cosine_sum(length, symmetry, &[0.54, 0.46])
+}
+
+pub fn blackman<T: Num + FromPrimitive>(length: usize, symmetry: Symmetry) -> Vec<T> {
+    cosine_sum(length, symmetry, &[0.42, 0.5, 0.08])
+}
+
+/// Four-term Blackman-Harris window, side lobes below -92 dB.
+pub fn blackman_harris<T: Num + FromPrimitive>(length: usize, symmetry: Symmetry) -> Vec<T> {
+    cosine_sum(length, symmetry, &[0.35875, 0.48829, 0.14128, 0.01168])
+}
+
+/// Flat-top window for accurate amplitude readings of tones falling between bins. Note
+/// that some of its samples are slightly negative.
+pub fn flat_top<T: Num + FromPrimitive>(length: usize, symmetry: Symmetry) -> Vec<T> {
+    cosine_sum(
+        length,
+        symmetry,
+        &[
+            0.21557895,
+            0.41663158,
+            0.277263158,
+            0.083578947,
+            0.006947368,
+        ],
+    )
+}
+
+/// Kaiser window; `beta` trades main-lobe width for side-lobe level (0 is rectangular,
+/// about 8.6 resembles Blackman).
+pub fn kaiser<T: Num + FromPrimitive>(length: usize, beta: f64, symmetry: Symmetry) -> Vec<T> {
+    let scale = bessel_i0(beta);
+    build(length, symmetry, |x| {
+        let r = 2.0 * x - 1.0;
+        bessel_i0(beta * (1.0 - r * r).max(0.0).sqrt()) / scale
+    })
+}
+
+/// Tukey (tapered cosine) window: a cosine taper over a fraction `alpha` of the length and
+/// flat in between. `alpha <= 0` gives a rectangular window and `alpha >= 1` a Hann window.
+pub fn tukey<T: Num + FromPrimitive>(length: usize, alpha: f64, symmetry: Symmetry) -> Vec<T> {
+    build(length, symmetry, |x| {
+        let edge = x.min(1.0 - x);
+        match alpha <= 0.0 || edge >= alpha / 2.0 {
+            true => 1.0,
+            false => 0.5 - 0.5 * (TAU * edge / alpha.min(1.0)).cos(),
+        }
+    })
+}
+
+/// Gaussian window with standard deviation `std` given in samples.
+///
+/// # Panics
+///
+/// If `std` is not positive.
+pub fn gaussian<T: Num + FromPrimitive>(length: usize, std: f64, symmetry: Symmetry) -> Vec<T> {
+    assert!(std > 0.0, "standard deviation must be positive");
+    let span = span(length, symmetry) as f64;
+    build(length, symmetry, |x| {
+        let n = (x - 0.5) * span / std;
+        (-0.5 * n * n).exp()
+    })
+}
+
+/// Multiplies `x` by `window` sample by sample.
+///
+/// # Panics
+///
+/// If the lengths differ.
+pub fn apply<T: Num + Copy>(x: &mut [T], window: &[T]) {
+    assert_eq!(
+        x.len(),
+        window.len(),
+        "window length does not match the signal"
+    );
+    x.iter_mut()
+        .zip(window.iter())
+        .for_each(|(v, &w)| *v = *v * w);
+}
+
+/// [`apply`] for complex samples.
+pub fn apply_complex<T: Num + Copy>(x: &mut [Complex<T>], window: &[T]) {
+    assert_eq!(
+        x.len(),
+        window.len(),
+        "window length does not match the signal"
+    );
+    x.iter_mut().zip(window.iter()).for_each(|(v, &w)| {
+        v.re = v.re * w;
+        v.im = v.im * w;
+    });
+}
+
+/// Modified Bessel function of the first kind, order zero, from its power series
+/// `Σ ((x/2)^k / k!)²`, summed until the terms stop contributing.
+pub fn bessel_i0(x: f64) -> f64 {
+    let half = x / 2.0;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut k = 1.0;
+    while term > sum * f64::EPSILON {
+        term *= (half / k) * (half / k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+/// `Σ (-1)^k a_k cos(2πkn / D)`, the family Hann, Hamming and the Blackman variants share.
+fn cosine_sum<T: Num + FromPrimitive>(
+    length: usize,
+    symmetry: Symmetry,
+    coefficients: &[f64],
+) -> Vec<T> {
+    build(length, symmetry, |x| {
+        coefficients
+            .iter()
+            .enumerate()
+            .map(|(k, a)| {
+                let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                sign * a * (TAU * k as f64 * x).cos()
+            })
+            .sum()
+    })
+}
+
+/// The denominator `D` that maps sample `n` to the position `n / D` in `[0, 1]`.
+fn span(length: usize, symmetry: Symmetry) -> usize {
+    match symmetry {
+        Symmetry::Symmetric => length - 1,
+        Symmetry::Periodic => length,
+    }
+}
+
+/// Evaluates `f` at the normalized position of every sample.
+fn build<T: Num + FromPrimitive>(
+    length: usize,
+    symmetry: Symmetry,
+    f: impl Fn(f64) -> f64,
+) -> Vec<T> {
+    match length {
+        0 => Vec::new(),
+        1 => vec![T::one()],
+        _ => {
+            let span = span(length, symmetry) as f64;
+            (0..length)
+                .map(|n| T::from_f64(f(n as f64 / span)).unwrap())
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        assert!(
+            a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn hann_test() {
+        assert_close(
+            &hann(5, Symmetry::Symmetric),
+            &[0.0, 0.5, 1.0, 0.5, 0.0],
+            1e-15,
+        );
+        assert_close(&hann(4, Symmetry::Periodic), &[0.0, 0.5, 1.0, 0.5], 1e-15);
+    }
+
+    #[test]
+    fn hamming_blackman_test() {
+        assert_close(
+            &hamming(5, Symmetry::Symmetric),
+            &[0.08, 0.54, 1.0, 0.54, 0.08],
+            1e-15,
+        );
+        assert_close(
+            &blackman(5, Symmetry::Symmetric),
+            &[0.0, 0.34, 1.0, 0.34, 0.0],
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn blackman_harris_flat_top_test() {
+        let bh: Vec<f64> = blackman_harris(7, Symmetry::Symmetric);
+        assert!((bh[0] - 6e-5).abs() < 1e-12 && (bh[3] - 1.0).abs() < 1e-12);
+        let ft: Vec<f64> = flat_top(7, Symmetry::Symmetric);
+        assert!((ft[3] - 1.0).abs() < 1e-8);
+        assert!(ft[0] < 0.0);
+    }
+
+    #[test]
+    fn kaiser_test() {
+        assert!((bessel_i0(0.0) - 1.0).abs() < 1e-15);
+        assert!((bessel_i0(1.0) - 1.2660658777520082).abs() < 1e-14);
+        assert!((bessel_i0(5.0) - 27.239871823604442).abs() < 1e-11);
+        assert_close(&kaiser(5, 0.0, Symmetry::Symmetric), &[1.0; 5], 1e-15);
+        assert_close(
+            &kaiser(5, 5.0, Symmetry::Symmetric),
+            &[0.03671089, 0.55285177, 1.0, 0.55285177, 0.03671089],
+            1e-8,
+        );
+    }
+
+    #[test]
+    fn tukey_test() {
+        assert_close(
+            &tukey(5, 0.5, Symmetry::Symmetric),
+            &[0.0, 1.0, 1.0, 1.0, 0.0],
+            1e-15,
+        );
+        assert_close(&tukey(6, 0.0, Symmetry::Symmetric), &[1.0; 6], 1e-15);
+        assert_close(
+            &tukey(9, 1.0, Symmetry::Symmetric),
+            &hann(9, Symmetry::Symmetric),
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn gaussian_test() {
+        let g: Vec<f64> = gaussian(5, 1.0, Symmetry::Symmetric);
+        let e = |x: f64| (-0.5 * x * x).exp();
+        assert_close(&g, &[e(2.0), e(1.0), 1.0, e(1.0), e(2.0)], 1e-15);
+    }
+
+    #[test]
+    #[should_panic(expected = "standard deviation must be positive")]
+    fn gaussian_zero_std_test() {
+        let _: Vec<Decimal> = gaussian(5, 0.0, Symmetry::Symmetric);
+    }
+
+    #[test]
+    fn symmetry_test() {
+        let sym: Vec<f64> = blackman(9, Symmetry::Symmetric);
+        let periodic: Vec<f64> = blackman(8, Symmetry::Periodic);
+        assert_close(&sym[..8], &periodic, 1e-15);
+        assert!((0..9).all(|i| (sym[i] - sym[8 - i]).abs() < 1e-15));
+        assert!(hann::<f64>(0, Symmetry::Periodic).is_empty());
+        assert_eq!(hann::<f64>(1, Symmetry::Symmetric), vec![1.0]);
+    }
+
+    #[test]
+    fn generic_types_test() {
+        let w: Vec<Decimal> = hann(4, Symmetry::Periodic);
+        assert!((w[1] - dec!(0.5)).abs() < dec!(1e-15));
+        let w: Vec<f32> = hamming(3, Symmetry::Symmetric);
+        assert!((w[1] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn apply_test() {
+        let mut x = vec![2.0, 2.0, 2.0, 2.0];
+        apply(&mut x, &hann(4, Symmetry::Periodic));
+        assert_close(&x, &[0.0, 1.0, 2.0, 1.0], 1e-15);
+
+        let mut c = vec![Complex { re: 1.0, im: -1.0 }; 2];
+        apply_complex(&mut c, &[0.5, 2.0]);
+        assert_eq!(
+            c,
+            vec![Complex { re: 0.5, im: -0.5 }, Complex { re: 2.0, im: -2.0 }]
+        );
+    }
+}
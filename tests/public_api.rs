@@ -0,0 +1,66 @@
+use rsmath::complex::Complex;
+use rsmath::fft;
+use rsmath::matrix::{Matrix2D, Matrix2DError};
+
+#[test]
+fn complex_path_test() {
+    let cnum = Complex { re: 1.0, im: 2.0 };
+    let res = cnum.multiply(&Complex { re: 3.0, im: -1.0 });
+    assert_eq!(res, Complex { re: 5.0, im: 5.0 });
+}
+
+#[test]
+fn matrix_path_test() {
+    let mat = Matrix2D::new(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    assert_eq!(mat.scalar_mult(2)[1], vec![6, 8]);
+    assert_eq!(
+        Matrix2D::<i32>::new(vec![]),
+        Err(Matrix2DError::EmptyMatrix)
+    );
+}
+
+#[test]
+fn fft_path_test() {
+    let x = vec![
+        Complex { re: 1.0, im: 0.0 },
+        Complex { re: 1.0, im: 0.0 },
+        Complex { re: 1.0, im: 0.0 },
+        Complex { re: 1.0, im: 0.0 },
+    ];
+    let res: Vec<Complex<f64>> = fft::fft(x);
+    assert!((res[0].re - 4.0).abs() < 1e-12);
+    let back = fft::ifft_normalized(res.clone(), fft::Normalization::Backward);
+    assert!(back.iter().all(|c| (c.re - 1.0).abs() < 1e-12));
+    assert!(res[1..]
+        .iter()
+        .all(|c| c.re.abs() < 1e-12 && c.im.abs() < 1e-12));
+}
+
+#[test]
+fn root_reexports_test() {
+    let a: rsmath::Complex<i32> = Complex { re: 1, im: 1 };
+    let b: rsmath::Matrix2D<i32> = Matrix2D::new(vec![vec![a.re]]).unwrap();
+    assert!(matches!(b.det(), Ok(1)));
+    let err: rsmath::Matrix2DError = Matrix2DError::NotSquare;
+    assert_eq!(err, Matrix2DError::NotSquare);
+}
+
+#[test]
+fn prelude_test() {
+    use rsmath::prelude::*;
+
+    let x: Vec<Complex<f64>> = vec![Complex { re: 2.0, im: 0.0 }, Complex { re: 0.0, im: 0.0 }];
+    let res = fft(x);
+    assert!((res[0].re - 2.0).abs() < 1e-12 && (res[1].re - 2.0).abs() < 1e-12);
+    assert!(Matrix2D::new(vec![vec![1.0]]).is_ok());
+}
+
+#[test]
+fn fft2_path_test() {
+    let one = Complex { re: 1.0, im: 0.0 };
+    let mat = Matrix2D::new(vec![vec![one, one], vec![one, one]]).unwrap();
+    let mut res: Matrix2D<Complex<f64>> = fft::fft2(mat);
+    fft::fftshift2(&mut res);
+    assert!((res[1][1].re - 4.0).abs() < 1e-12);
+    assert!(res[0][0].re.abs() < 1e-12);
+}